@@ -36,6 +36,7 @@ fn spawn_player(mut commands: Commands) {
                     down: KeyCode::S.into(),
                     left: KeyCode::A.into(),
                     right: KeyCode::D.into(),
+                    ramp: None,
                 },
                 Action::Move,
             )])
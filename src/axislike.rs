@@ -8,7 +8,7 @@ use bevy::input::{
     keyboard::KeyCode,
 };
 use bevy::math::Vec2;
-use bevy::utils::FloatOrd;
+use bevy::utils::{Duration, FloatOrd};
 use serde::{Deserialize, Serialize};
 
 /// A single directional axis with a configurable trigger zone.
@@ -26,6 +26,33 @@ pub struct SingleAxis {
     pub positive_low: f32,
     /// Any axis value lower than this will trigger the input.
     pub negative_low: f32,
+    /// The minimum magnitude of the axis value once the input is triggered.
+    ///
+    /// This boosts small movements just outside of the trigger zone,
+    /// compensating for sticks with a large physical dead zone.
+    #[serde(default)]
+    pub anti_deadzone: f32,
+    /// The magnitude at which the axis value reaches its maximum, if any.
+    ///
+    /// When set, values between the trigger thresholds and this outer zone are linearly rescaled onto `[0, 1]`,
+    /// and values beyond it are clamped to 1.
+    #[serde(default)]
+    pub outer_zone: Option<f32>,
+    /// How the axis value ramps up once it leaves the dead zone
+    #[serde(default)]
+    pub deadzone_curve: DeadzoneCurve,
+    /// The number of discrete levels that the magnitude of the axis value is snapped to, if any.
+    ///
+    /// The levels are evenly spaced, from 0 to 1 inclusive: 3 levels produce magnitudes of 0, 0.5 and 1.
+    /// This is applied after all other processing, and is useful when inputs must be deterministic, such as in lockstep netcode.
+    /// Fewer than 2 levels leave the value unchanged.
+    #[serde(default)]
+    pub quantize: Option<u8>,
+    /// Should the magnitude of the axis value be reported, regardless of its direction?
+    ///
+    /// This is applied after all other processing.
+    #[serde(default)]
+    pub absolute: bool,
     /// The target value for this input, used for input mocking.
     ///
     /// WARNING: this field is ignored for the sake of [`Eq`] and [`Hash`](std::hash::Hash)
@@ -40,6 +67,11 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             positive_low: threshold,
             negative_low: -threshold,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: None,
         }
     }
@@ -54,10 +86,56 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: Some(value),
         }
     }
 
+    /// Returns this [`SingleAxis`] with the provided `anti_deadzone`.
+    #[must_use]
+    pub fn with_anti_deadzone(mut self, anti_deadzone: f32) -> SingleAxis {
+        self.anti_deadzone = anti_deadzone;
+        self
+    }
+
+    /// Returns this [`SingleAxis`] with symmetric `inner` and `outer` response zones.
+    ///
+    /// Values with a magnitude below `inner` are treated as zero,
+    /// magnitudes between `inner` and `outer` are linearly mapped onto `[0, 1]`,
+    /// and magnitudes beyond `outer` are clamped to 1.
+    #[must_use]
+    pub fn with_zones(mut self, inner: f32, outer: f32) -> SingleAxis {
+        self.positive_low = inner;
+        self.negative_low = -inner;
+        self.outer_zone = Some(outer);
+        self
+    }
+
+    /// Returns this [`SingleAxis`] with the provided [`DeadzoneCurve`].
+    #[must_use]
+    pub fn with_deadzone_curve(mut self, deadzone_curve: DeadzoneCurve) -> SingleAxis {
+        self.deadzone_curve = deadzone_curve;
+        self
+    }
+
+    /// Returns this [`SingleAxis`] with its value snapped to the provided number of discrete levels.
+    #[must_use]
+    pub fn with_quantize(mut self, steps: u8) -> SingleAxis {
+        self.quantize = Some(steps);
+        self
+    }
+
+    /// Returns this [`SingleAxis`], reporting only the magnitude of its value if `absolute` is `true`.
+    #[must_use]
+    pub fn with_absolute(mut self, absolute: bool) -> SingleAxis {
+        self.absolute = absolute;
+        self
+    }
+
     /// Creates a [`SingleAxis`] corresponding to horizontal [`MouseWheel`](bevy::input::mouse::MouseWheel) movement
     #[must_use]
     pub const fn mouse_wheel_x() -> SingleAxis {
@@ -65,6 +143,11 @@ impl SingleAxis {
             axis_type: AxisType::MouseWheel(MouseWheelAxisType::X),
             positive_low: 0.,
             negative_low: 0.,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: None,
         }
     }
@@ -76,6 +159,11 @@ impl SingleAxis {
             axis_type: AxisType::MouseWheel(MouseWheelAxisType::Y),
             positive_low: 0.,
             negative_low: 0.,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: None,
         }
     }
@@ -87,6 +175,11 @@ impl SingleAxis {
             axis_type: AxisType::MouseMotion(MouseMotionAxisType::X),
             positive_low: 0.,
             negative_low: 0.,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: None,
         }
     }
@@ -98,6 +191,11 @@ impl SingleAxis {
             axis_type: AxisType::MouseMotion(MouseMotionAxisType::Y),
             positive_low: 0.,
             negative_low: 0.,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
             value: None,
         }
     }
@@ -108,6 +206,11 @@ impl PartialEq for SingleAxis {
         self.axis_type == other.axis_type
             && FloatOrd(self.positive_low) == FloatOrd(other.positive_low)
             && FloatOrd(self.negative_low) == FloatOrd(other.negative_low)
+            && FloatOrd(self.anti_deadzone) == FloatOrd(other.anti_deadzone)
+            && self.outer_zone.map(FloatOrd) == other.outer_zone.map(FloatOrd)
+            && self.deadzone_curve == other.deadzone_curve
+            && self.quantize == other.quantize
+            && self.absolute == other.absolute
     }
 }
 impl Eq for SingleAxis {}
@@ -116,6 +219,33 @@ impl std::hash::Hash for SingleAxis {
         self.axis_type.hash(state);
         FloatOrd(self.positive_low).hash(state);
         FloatOrd(self.negative_low).hash(state);
+        FloatOrd(self.anti_deadzone).hash(state);
+        self.outer_zone.map(FloatOrd).hash(state);
+        self.deadzone_curve.hash(state);
+        self.quantize.hash(state);
+        self.absolute.hash(state);
+    }
+}
+
+/// How the value of a [`SingleAxis`] ramps up as it leaves the dead zone
+///
+/// The dead zone is bounded by the `positive_low` and `negative_low` thresholds,
+/// and the ramp ends at the `outer_zone` of the axis, or at a magnitude of 1 if that is not set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeadzoneCurve {
+    /// The raw value is used as soon as it leaves the dead zone, jumping from 0
+    ///
+    /// If an `outer_zone` is set, the value is rescaled linearly instead.
+    Hard,
+    /// The value is rescaled linearly, from 0 at the edge of the dead zone to 1 at the end of the ramp
+    Linear,
+    /// The value follows a smoothstep curve, easing out of the dead zone and into the end of the ramp
+    Smoothstep,
+}
+
+impl Default for DeadzoneCurve {
+    fn default() -> Self {
+        DeadzoneCurve::Hard
     }
 }
 
@@ -135,6 +265,12 @@ pub struct DualAxis {
     pub x: SingleAxis,
     /// The axis representing vertical movement.
     pub y: SingleAxis,
+    /// Should the circular range of this stick be stretched to fill a square?
+    ///
+    /// When `true`, [`InputStreams::input_axis_pair`](crate::input_streams::InputStreams::input_axis_pair)
+    /// applies [`DualAxisData::circle_to_square`], so that diagonals reach full speed on both axes.
+    #[serde(default)]
+    pub square_output: bool,
 }
 
 impl DualAxis {
@@ -153,6 +289,7 @@ impl DualAxis {
         DualAxis {
             x: SingleAxis::symmetric(x_axis_type, threshold),
             y: SingleAxis::symmetric(y_axis_type, threshold),
+            square_output: false,
         }
     }
 
@@ -170,6 +307,7 @@ impl DualAxis {
         DualAxis {
             x: SingleAxis::from_value(x_axis_type, x_value),
             y: SingleAxis::from_value(y_axis_type, y_value),
+            square_output: false,
         }
     }
 
@@ -198,6 +336,7 @@ impl DualAxis {
         DualAxis {
             x: SingleAxis::mouse_wheel_x(),
             y: SingleAxis::mouse_wheel_y(),
+            square_output: false,
         }
     }
 
@@ -206,8 +345,131 @@ impl DualAxis {
         DualAxis {
             x: SingleAxis::mouse_motion_x(),
             y: SingleAxis::mouse_motion_y(),
+            square_output: false,
         }
     }
+
+    /// Stretches the circular range of this stick to fill a square
+    ///
+    /// See [`DualAxis::square_output`] for more details.
+    #[must_use]
+    pub const fn with_square_output(mut self) -> DualAxis {
+        self.square_output = true;
+        self
+    }
+}
+
+/// A gesture that is triggered by rotating a stick around in a circle.
+///
+/// This is pressed when the stick has swept through at least `min_angle_swept` radians
+/// (in either direction) within the last `window`.
+/// The swept angle is measured using the [`InputHistory`](crate::input_history::InputHistory) resource,
+/// which only samples gamepad axes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CircleGesture {
+    /// The stick that is rotated
+    pub axis: DualAxis,
+    /// The angle in radians that the stick must sweep through, such as `TAU` for a full circle
+    pub min_angle_swept: f32,
+    /// The time within which the angle must be swept
+    pub window: Duration,
+}
+
+impl PartialEq for CircleGesture {
+    fn eq(&self, other: &Self) -> bool {
+        self.axis == other.axis
+            && FloatOrd(self.min_angle_swept) == FloatOrd(other.min_angle_swept)
+            && self.window == other.window
+    }
+}
+impl Eq for CircleGesture {}
+impl std::hash::Hash for CircleGesture {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.axis.hash(state);
+        FloatOrd(self.min_angle_swept).hash(state);
+        self.window.hash(state);
+    }
+}
+
+/// An axis crossing a threshold, which is pressed only on the frame that it crosses.
+///
+/// This fires when the value of the axis rises from below `threshold` to at or above it,
+/// and will not fire again until the axis has dropped back below the threshold.
+/// The previous value of the axis is read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AxisCross {
+    /// The axis that is checked
+    pub axis: AxisType,
+    /// The value that the axis must rise past
+    pub threshold: f32,
+}
+
+impl AxisCross {
+    /// Creates a new [`AxisCross`] for the provided `axis` and `threshold`
+    #[must_use]
+    pub fn new(axis: impl Into<AxisType>, threshold: f32) -> AxisCross {
+        AxisCross {
+            axis: axis.into(),
+            threshold,
+        }
+    }
+
+    /// The [`SingleAxis`] whose raw value is compared against the `threshold`, without any dead zone
+    #[must_use]
+    pub fn raw_axis(&self) -> SingleAxis {
+        SingleAxis::symmetric(self.axis, 0.0)
+    }
+}
+
+impl PartialEq for AxisCross {
+    fn eq(&self, other: &Self) -> bool {
+        self.axis == other.axis && FloatOrd(self.threshold) == FloatOrd(other.threshold)
+    }
+}
+impl Eq for AxisCross {}
+impl std::hash::Hash for AxisCross {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.axis.hash(state);
+        FloatOrd(self.threshold).hash(state);
+    }
+}
+
+/// Both gamepad sticks pushed in roughly opposite directions at once.
+///
+/// This is pressed while both sticks have a magnitude greater than `threshold`,
+/// and the dot product of their directions is below `-threshold`.
+/// The sticks are read using the [`DEFAULT_DEADZONE`](DualAxis::DEFAULT_DEADZONE) of [`DualAxis::left_stick`] and [`DualAxis::right_stick`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StickOpposition {
+    /// How far both sticks must be pushed, and how closely they must oppose each other
+    pub threshold: f32,
+}
+
+impl StickOpposition {
+    /// Are the `left` and `right` stick positions opposed, according to the `threshold`?
+    #[must_use]
+    pub fn opposed(&self, left: Vec2, right: Vec2) -> bool {
+        if left.length() <= self.threshold || right.length() <= self.threshold {
+            return false;
+        }
+
+        match (left.try_normalize(), right.try_normalize()) {
+            (Some(left), Some(right)) => left.dot(right) < -self.threshold,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for StickOpposition {
+    fn eq(&self, other: &Self) -> bool {
+        FloatOrd(self.threshold) == FloatOrd(other.threshold)
+    }
+}
+impl Eq for StickOpposition {}
+impl std::hash::Hash for StickOpposition {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        FloatOrd(self.threshold).hash(state);
+    }
 }
 
 #[allow(clippy::doc_markdown)] // False alarm because it thinks DPad is an un-quoted item
@@ -227,9 +489,26 @@ pub struct VirtualDPad {
     pub left: InputKind,
     /// The input that represents the right direction in this virtual DPad
     pub right: InputKind,
+    /// How long digital members of this virtual DPad take to ramp up to their full value
+    ///
+    /// When set, a held button reports the fraction of `ramp` that it has been held for,
+    /// letting a key stand in for an analog trigger or stick.
+    /// Analog members ([`InputKind::SingleAxis`] and [`InputKind::DualAxis`]) are never ramped.
+    /// Requires the [`InputHistory`](crate::input_history::InputHistory) resource.
+    #[serde(default)]
+    pub ramp: Option<Duration>,
 }
 
 impl VirtualDPad {
+    /// The bit set by [`InputStreams::dpad_bitmask`](crate::input_streams::InputStreams::dpad_bitmask) when `up` is pressed
+    pub const UP_BIT: u8 = 1 << 0;
+    /// The bit set by [`InputStreams::dpad_bitmask`](crate::input_streams::InputStreams::dpad_bitmask) when `down` is pressed
+    pub const DOWN_BIT: u8 = 1 << 1;
+    /// The bit set by [`InputStreams::dpad_bitmask`](crate::input_streams::InputStreams::dpad_bitmask) when `left` is pressed
+    pub const LEFT_BIT: u8 = 1 << 2;
+    /// The bit set by [`InputStreams::dpad_bitmask`](crate::input_streams::InputStreams::dpad_bitmask) when `right` is pressed
+    pub const RIGHT_BIT: u8 = 1 << 3;
+
     /// Generates a [`VirtualDPad`] corresponding to the arrow keyboard keycodes
     pub fn arrow_keys() -> VirtualDPad {
         VirtualDPad {
@@ -237,6 +516,7 @@ impl VirtualDPad {
             down: InputKind::Keyboard(KeyCode::Down),
             left: InputKind::Keyboard(KeyCode::Left),
             right: InputKind::Keyboard(KeyCode::Right),
+            ramp: None,
         }
     }
 
@@ -247,6 +527,7 @@ impl VirtualDPad {
             down: InputKind::Keyboard(KeyCode::S),
             left: InputKind::Keyboard(KeyCode::A),
             right: InputKind::Keyboard(KeyCode::D),
+            ramp: None,
         }
     }
 
@@ -258,6 +539,7 @@ impl VirtualDPad {
             down: InputKind::GamepadButton(GamepadButtonType::DPadDown),
             left: InputKind::GamepadButton(GamepadButtonType::DPadLeft),
             right: InputKind::GamepadButton(GamepadButtonType::DPadRight),
+            ramp: None,
         }
     }
 
@@ -270,6 +552,36 @@ impl VirtualDPad {
             down: InputKind::GamepadButton(GamepadButtonType::South),
             left: InputKind::GamepadButton(GamepadButtonType::West),
             right: InputKind::GamepadButton(GamepadButtonType::East),
+            ramp: None,
+        }
+    }
+
+    /// Generates a [`VirtualDPad`] driven by the analog axes of the provided `stick`
+    ///
+    /// Each direction reads only its own side of the corresponding axis of the `stick`,
+    /// so only the directions that the stick is tilted towards are pressed.
+    /// The axis pair of this dpad is read from the analog axes,
+    /// so a half-tilted stick produces a half-magnitude pair rather than snapping to `1.0`.
+    #[must_use]
+    pub fn stick_dpad(stick: DualAxis) -> VirtualDPad {
+        VirtualDPad {
+            up: InputKind::SingleAxis(SingleAxis {
+                negative_low: -1.0,
+                ..stick.y
+            }),
+            down: InputKind::SingleAxis(SingleAxis {
+                positive_low: 1.0,
+                ..stick.y
+            }),
+            left: InputKind::SingleAxis(SingleAxis {
+                positive_low: 1.0,
+                ..stick.x
+            }),
+            right: InputKind::SingleAxis(SingleAxis {
+                negative_low: -1.0,
+                ..stick.x
+            }),
+            ramp: None,
         }
     }
 
@@ -280,6 +592,7 @@ impl VirtualDPad {
             down: InputKind::MouseWheel(MouseWheelDirection::Down),
             left: InputKind::MouseWheel(MouseWheelDirection::Left),
             right: InputKind::MouseWheel(MouseWheelDirection::Right),
+            ramp: None,
         }
     }
 
@@ -290,8 +603,18 @@ impl VirtualDPad {
             down: InputKind::MouseMotion(MouseMotionDirection::Down),
             left: InputKind::MouseMotion(MouseMotionDirection::Left),
             right: InputKind::MouseMotion(MouseMotionDirection::Right),
+            ramp: None,
         }
     }
+
+    /// Ramps digital members of this virtual DPad up to their full value over `ramp`
+    ///
+    /// See [`VirtualDPad::ramp`] for more details.
+    #[must_use]
+    pub fn with_ramp(mut self, ramp: Duration) -> VirtualDPad {
+        self.ramp = Some(ramp);
+        self
+    }
 }
 
 /// The type of axis used by a [`UserInput`](crate::user_input::UserInput).
@@ -503,6 +826,28 @@ impl DualAxisData {
     pub fn clamp_length(&mut self, max: f32) {
         self.xy = self.xy.clamp_length_max(max);
     }
+
+    /// Stretches a position within the unit circle to fill the unit square
+    ///
+    /// Positions on the edge of the circle are mapped to the edge of the square,
+    /// so a full diagonal of `(0.707, 0.707)` becomes `(1.0, 1.0)`.
+    /// Positions outside of the unit circle are clamped to it first.
+    /// This uses the elliptical grid mapping, which keeps the axes themselves unchanged.
+    #[must_use]
+    pub fn circle_to_square(self) -> DualAxisData {
+        let Vec2 { x: u, y: v } = self.xy.clamp_length_max(1.0);
+        let two_sqrt_two = 2.0 * std::f32::consts::SQRT_2;
+        let u2_minus_v2 = u * u - v * v;
+
+        // Rounding errors can push the radicands slightly below zero
+        let half_root = |radicand: f32| 0.5 * radicand.max(0.0).sqrt();
+        let x = half_root(2.0 + u2_minus_v2 + two_sqrt_two * u)
+            - half_root(2.0 + u2_minus_v2 - two_sqrt_two * u);
+        let y = half_root(2.0 - u2_minus_v2 + two_sqrt_two * v)
+            - half_root(2.0 - u2_minus_v2 - two_sqrt_two * v);
+
+        DualAxisData::new(x, y)
+    }
 }
 
 impl From<DualAxisData> for Vec2 {
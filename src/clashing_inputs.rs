@@ -54,24 +54,71 @@ impl UserInput {
     fn clashes(&self, other: &UserInput) -> bool {
         use UserInput::*;
 
-        match self {
-            Single(self_button) => match other {
-                Single(_) => false,
-                Chord(other_chord) => button_chord_clash(self_button, other_chord),
-                VirtualDPad(other_dpad) => dpad_button_clash(other_dpad, self_button),
-            },
-            Chord(self_chord) => match other {
-                Single(other_button) => button_chord_clash(other_button, self_chord),
-                Chord(other_chord) => chord_chord_clash(self_chord, other_chord),
-                VirtualDPad(other_dpad) => dpad_chord_clash(other_dpad, self_chord),
-            },
-            VirtualDPad(self_dpad) => match other {
-                Single(other_button) => dpad_button_clash(self_dpad, other_button),
-                Chord(other_chord) => dpad_chord_clash(self_dpad, other_chord),
-                VirtualDPad(other_dpad) => dpad_dpad_clash(self_dpad, other_dpad),
-            },
+        match (self, other) {
+            (Single(_), Single(_)) => false,
+            (Single(self_button), Chord(other_chord)) => {
+                button_chord_clash(self_button, other_chord)
+            }
+            (Single(self_button), VirtualDPad(other_dpad)) => {
+                dpad_button_clash(other_dpad, self_button)
+            }
+            (Chord(self_chord), Single(other_button)) => {
+                button_chord_clash(other_button, self_chord)
+            }
+            (Chord(self_chord), Chord(other_chord)) => chord_chord_clash(self_chord, other_chord),
+            (Chord(self_chord), VirtualDPad(other_dpad)) => {
+                dpad_chord_clash(other_dpad, self_chord)
+            }
+            (VirtualDPad(self_dpad), Single(other_button)) => {
+                dpad_button_clash(self_dpad, other_button)
+            }
+            (VirtualDPad(self_dpad), Chord(other_chord)) => {
+                dpad_chord_clash(self_dpad, other_chord)
+            }
+            (VirtualDPad(self_dpad), VirtualDPad(other_dpad)) => {
+                dpad_dpad_clash(self_dpad, other_dpad)
+            }
+            // Toggles, charges, debounced inputs and double-tap-holds are driven by their own press history,
+            // and never clash with other inputs
+            (Toggle { .. } | Charge { .. } | Debounced { .. } | DoubleTapHold { .. }, _)
+            | (_, Toggle { .. } | Charge { .. } | Debounced { .. } | DoubleTapHold { .. }) => false,
+            // Hold-taps and staircases depend on the order in which their buttons are pressed
+            (HoldTap { .. } | Staircase { .. }, _) | (_, HoldTap { .. } | Staircase { .. }) => {
+                false
+            }
+            // Edge-triggered inputs are only pressed for a single frame
+            (OnEdge { .. }, _) | (_, OnEdge { .. }) => false,
+            // Shakes depend on the history of the axis, rather than its current state
+            (Shake { .. }, _) | (_, Shake { .. }) => false,
+            // Aliases cannot be resolved without the `InputAlias` resource, so are never considered to clash
+            (Alias(_), _) | (_, Alias(_)) => false,
+            // Gestures depend on the history of the stick, rather than its current state
+            (CircleGesture(_), _) | (_, CircleGesture(_)) => false,
+            (Pinch, _) | (_, Pinch) => false,
+            // Crossings depend on the previous value of the axis, rather than its current state
+            (AxisCross(_), _) | (_, AxisCross(_)) => false,
+            // Stick oppositions span both sticks, which are not otherwise combined
+            (StickOpposition(_), _) | (_, StickOpposition(_)) => false,
+            // Scrolling is only read while its button is held, so it never completes another input
+            (ScrollWhileHeld { .. }, _) | (_, ScrollWhileHeld { .. }) => false,
+            // Which preferred input is read depends on the connected devices
+            (Preferred(_), _) | (_, Preferred(_)) => false,
+            // Custom inputs are opaque, so their clashes cannot be detected
+            (Custom(_), _) | (_, Custom(_)) => false,
         }
     }
+
+    /// Which of the `existing` inputs would clash with `self`?
+    ///
+    /// Uses the same rules as the [`ClashStrategy`] handling: inputs clash if one contains a strict subset of the other's buttons.
+    /// This does not depend on the current input, so is well-suited to warning about conflicts when rebinding.
+    #[must_use]
+    pub fn clashing_inputs<'b>(
+        &self,
+        existing: impl Iterator<Item = &'b UserInput>,
+    ) -> Vec<&'b UserInput> {
+        existing.filter(|input| self.clashes(input)).collect()
+    }
 }
 
 impl<A: Actionlike> InputMap<A> {
@@ -265,17 +312,18 @@ fn chord_chord_clash(chord_a: &PetitSet<InputKind, 8>, chord_b: &PetitSet<InputK
 fn check_clash<A: Actionlike>(clash: &Clash<A>, input_streams: &InputStreams) -> Option<Clash<A>> {
     let mut actual_clash: Clash<A> = Clash::from_indexes(clash.index_a, clash.index_b);
 
+    // Any hooks were already run when the actions were found to be pressed, so they are skipped here
     // For all inputs that were actually pressed that match action A
     for input_a in clash
         .inputs_a
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .filter(|&input| input_streams.unchecked_input_pressed(input))
     {
         // For all inputs that were actually pressed that match action B
         for input_b in clash
             .inputs_b
             .iter()
-            .filter(|&input| input_streams.input_pressed(input))
+            .filter(|&input| input_streams.unchecked_input_pressed(input))
         {
             // If a clash was detected,
             if input_a.clashes(input_b) {
@@ -303,13 +351,13 @@ fn resolve_clash<A: Actionlike>(
     let reasons_a_is_pressed: Vec<&UserInput> = clash
         .inputs_a
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .filter(|&input| input_streams.unchecked_input_pressed(input))
         .collect();
 
     let reasons_b_is_pressed: Vec<&UserInput> = clash
         .inputs_b
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .filter(|&input| input_streams.unchecked_input_pressed(input))
         .collect();
 
     // Clashes are spurious if the actions are pressed for any non-clashing reason
@@ -398,6 +446,7 @@ mod tests {
                 down: Down.into(),
                 left: Left.into(),
                 right: Right.into(),
+                ramp: None,
             },
             MoveDPad,
         );
@@ -428,6 +477,7 @@ mod tests {
                 down: X.into(),
                 left: Y.into(),
                 right: Z.into(),
+                ramp: None,
             }
             .into();
             let abcd_dpad: UserInput = VirtualDPad {
@@ -435,6 +485,7 @@ mod tests {
                 down: B.into(),
                 left: C.into(),
                 right: D.into(),
+                ramp: None,
             }
             .into();
 
@@ -444,6 +495,7 @@ mod tests {
                 down: Down.into(),
                 left: Left.into(),
                 right: Right.into(),
+                ramp: None,
             }
             .into();
 
@@ -459,6 +511,17 @@ mod tests {
             assert!(ctrl_up.clashes(&directions_dpad));
         }
 
+        #[test]
+        fn clashing_inputs_listing() {
+            let s: UserInput = S.into();
+            let ctrl_s = UserInput::chord([LControl, S]);
+            let ctrl_w = UserInput::chord([LControl, W]);
+            let existing = [ctrl_s.clone(), ctrl_w, W.into()];
+
+            assert_eq!(s.clashing_inputs(existing.iter()), vec![&ctrl_s]);
+            assert_eq!(ctrl_s.clashing_inputs([s.clone()].iter()), vec![&s]);
+        }
+
         #[test]
         fn button_chord_clash_construction() {
             let input_map = test_input_map();
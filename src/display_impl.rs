@@ -1,6 +1,7 @@
 //! Containment module for boring implmentations of the [`Display`] trait
 
 use crate::axislike::VirtualDPad;
+use crate::input_alias::AliasId;
 use crate::user_input::{InputKind, UserInput};
 use std::fmt::Display;
 
@@ -23,6 +24,7 @@ impl Display for UserInput {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 write!(
                     f,
@@ -30,6 +32,36 @@ impl Display for UserInput {
                     up, down, left, right
                 )
             }
+            UserInput::Toggle { input } => write!(f, "Toggle({input})"),
+            UserInput::Charge { input, max } => write!(f, "Charge({input}, {max:?})"),
+            UserInput::HoldTap { hold, tap } => write!(f, "HoldTap({hold}, {tap})"),
+            UserInput::Staircase { steps } => {
+                let steps: Vec<String> = steps.iter().map(ToString::to_string).collect();
+                write!(f, "Staircase({})", steps.join(", "))
+            }
+            UserInput::Shake {
+                axis,
+                reversals,
+                window,
+            } => write!(f, "Shake({axis:?}, {reversals}, {window:?})"),
+            UserInput::Debounced { input, cooldown } => {
+                write!(f, "Debounced({input}, {cooldown:?})")
+            }
+            UserInput::DoubleTapHold { input, tap_window } => {
+                write!(f, "DoubleTapHold({input}, {tap_window:?})")
+            }
+            UserInput::OnEdge { input, edge } => write!(f, "OnEdge({input}, {edge:?})"),
+            UserInput::Alias(AliasId(id)) => write!(f, "Alias({id})"),
+            UserInput::CircleGesture(gesture) => write!(f, "{gesture:?}"),
+            UserInput::Pinch => write!(f, "Pinch"),
+            UserInput::AxisCross(axis_cross) => write!(f, "{axis_cross:?}"),
+            UserInput::StickOpposition(stick_opposition) => write!(f, "{stick_opposition:?}"),
+            UserInput::ScrollWhileHeld { button } => write!(f, "ScrollWhileHeld({button:?})"),
+            UserInput::Preferred(inputs) => {
+                let inputs: Vec<String> = inputs.iter().map(ToString::to_string).collect();
+                write!(f, "Preferred({})", inputs.join(", "))
+            }
+            UserInput::Custom(_) => write!(f, "Custom"),
         }
     }
 }
@@ -44,6 +76,8 @@ impl Display for InputKind {
             InputKind::MouseWheel(button) => write!(f, "{button:?}"),
             InputKind::MouseMotion(button) => write!(f, "{button:?}"),
             InputKind::Keyboard(button) => write!(f, "{button:?}"),
+            InputKind::KeyLocation(scan_code) => write!(f, "{scan_code:?}"),
+            InputKind::TouchFinger { index } => write!(f, "TouchFinger({index})"),
         }
     }
 }
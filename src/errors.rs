@@ -1,4 +1,4 @@
-//! Errors that may occur when working with 2D coordinates
+//! Errors that may occur when working with 2D coordinates or input aliases
 
 use derive_more::{Display, Error};
 
@@ -11,3 +11,10 @@ use derive_more::{Display, Error};
 /// In almost all cases, the correct way to handle this error is to simply not change the rotation.
 #[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
 pub struct NearlySingularConversion;
+
+/// The supplied input would create a cycle of aliases
+///
+/// This error is produced by [`InputAlias::insert`](crate::input_alias::InputAlias::insert)
+/// when a [`UserInput::Alias`](crate::user_input::UserInput::Alias) would end up referring back to itself.
+#[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
+pub struct CyclicAlias;
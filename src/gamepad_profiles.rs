@@ -0,0 +1,151 @@
+//! Per-device tuning of gamepads, stored in the [`GamepadProfiles`], [`GamepadCalibration`], [`GamepadButtonRemap`] and [`DisconnectGrace`] resources.
+
+use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadButtonType};
+use bevy::utils::{Duration, HashMap};
+use serde::{Deserialize, Serialize};
+
+/// How the raw axis values of a single gamepad are adjusted before they are used
+///
+/// This is applied to every gamepad axis of that gamepad,
+/// before the thresholds of any [`SingleAxis`](crate::axislike::SingleAxis) are checked.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisProfile {
+    /// The factor that raw axis values are multiplied by
+    pub sensitivity: f32,
+    /// Raw axis values with a magnitude at or below this are treated as `0.0`
+    pub deadzone: f32,
+}
+
+impl AxisProfile {
+    /// Applies this profile to the `raw` value of an axis
+    #[must_use]
+    pub fn apply(&self, raw: f32) -> f32 {
+        if raw.abs() <= self.deadzone {
+            0.0
+        } else {
+            raw * self.sensitivity
+        }
+    }
+}
+
+impl Default for AxisProfile {
+    fn default() -> Self {
+        AxisProfile {
+            sensitivity: 1.0,
+            deadzone: 0.0,
+        }
+    }
+}
+
+/// A resource that stores the [`AxisProfile`] of each [`Gamepad`]
+///
+/// Gamepads without a registered profile, or all gamepads if this resource does not exist, use their raw axis values.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GamepadProfiles {
+    profiles: HashMap<Gamepad, AxisProfile>,
+}
+
+impl GamepadProfiles {
+    /// Registers the `profile` for the provided `gamepad`, replacing any existing profile
+    pub fn insert(&mut self, gamepad: Gamepad, profile: AxisProfile) {
+        self.profiles.insert(gamepad, profile);
+    }
+
+    /// Fetches the [`AxisProfile`] registered for the `gamepad`, if any
+    #[must_use]
+    pub fn get(&self, gamepad: Gamepad) -> Option<&AxisProfile> {
+        self.profiles.get(&gamepad)
+    }
+
+    /// Removes the [`AxisProfile`] registered for the `gamepad`, returning it if it existed
+    pub fn remove(&mut self, gamepad: Gamepad) -> Option<AxisProfile> {
+        self.profiles.remove(&gamepad)
+    }
+}
+
+/// A resource that stores the resting position of each gamepad axis
+///
+/// These offsets are subtracted from the raw axis values before any [`AxisProfile`] is applied,
+/// correcting for sticks that drift away from zero.
+/// Record the current positions using [`MutableInputStreams::calibrate_gamepad_center`](crate::input_streams::MutableInputStreams::calibrate_gamepad_center).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GamepadCalibration {
+    centers: HashMap<GamepadAxis, f32>,
+}
+
+impl GamepadCalibration {
+    /// Records `center` as the resting position of the `axis`, replacing any existing value
+    pub fn insert(&mut self, axis: GamepadAxis, center: f32) {
+        self.centers.insert(axis, center);
+    }
+
+    /// Fetches the resting position recorded for the `axis`, if any
+    #[must_use]
+    pub fn get(&self, axis: GamepadAxis) -> Option<f32> {
+        self.centers.get(&axis).copied()
+    }
+
+    /// Returns the `raw` value of the `axis`, relative to its recorded resting position
+    #[must_use]
+    pub fn apply(&self, axis: GamepadAxis, raw: f32) -> f32 {
+        raw - self.get(axis).unwrap_or_default()
+    }
+
+    /// Removes the resting positions of every axis, restoring the raw axis values
+    pub fn clear(&mut self) {
+        self.centers.clear();
+    }
+}
+
+/// A resource that remaps gamepad buttons before they are matched
+///
+/// Each entry maps a bound button to the physical button that is read in its place,
+/// such as swapping [`South`](GamepadButtonType::South) and [`East`](GamepadButtonType::East)
+/// to switch between Xbox and Nintendo face button layouts.
+/// Buttons without an entry are read as-is.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GamepadButtonRemap {
+    remaps: HashMap<GamepadButtonType, GamepadButtonType>,
+}
+
+impl GamepadButtonRemap {
+    /// Reads the `physical` button whenever the `bound` button is checked, replacing any existing remap of `bound`
+    pub fn insert(&mut self, bound: GamepadButtonType, physical: GamepadButtonType) {
+        self.remaps.insert(bound, physical);
+    }
+
+    /// Swaps the buttons `a` and `b`, so that each is read in place of the other
+    pub fn swap(&mut self, a: GamepadButtonType, b: GamepadButtonType) {
+        self.insert(a, b);
+        self.insert(b, a);
+    }
+
+    /// The physical button that is read in place of the `bound` button
+    ///
+    /// Returns `bound` itself if it has not been remapped.
+    #[must_use]
+    pub fn get(&self, bound: GamepadButtonType) -> GamepadButtonType {
+        self.remaps.get(&bound).copied().unwrap_or(bound)
+    }
+
+    /// Removes the remap of the `bound` button, returning the physical button it was mapped to if it existed
+    pub fn remove(&mut self, bound: GamepadButtonType) -> Option<GamepadButtonType> {
+        self.remaps.remove(&bound)
+    }
+
+    /// Removes every remap, restoring the identity mapping
+    pub fn clear(&mut self) {
+        self.remaps.clear();
+    }
+}
+
+/// A resource that controls how long a disconnected gamepad keeps its last-known state
+///
+/// While within this grace period, the buttons and axes of a disconnected gamepad are read
+/// as they were on the last frame it was connected, rather than snapping to zero.
+/// This prevents a sudden stop when a controller briefly loses its connection mid-action.
+/// The last-known state is recorded by the [`InputHistory`](crate::input_history::InputHistory).
+///
+/// Defaults to [`Duration::ZERO`], which releases every input as soon as the gamepad disconnects.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DisconnectGrace(pub Duration);
@@ -0,0 +1,68 @@
+//! Named [`UserInput`]s, which can be reused across bindings with [`UserInput::Alias`].
+
+use crate::errors::CyclicAlias;
+use crate::user_input::UserInput;
+
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// A handle to a [`UserInput`] stored in the [`InputAlias`] resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AliasId(pub u32);
+
+/// A resource that maps each [`AliasId`] to the [`UserInput`] that it stands for
+///
+/// [`UserInput::Alias`] inputs are resolved by looking up this registry.
+/// If no input is registered for an alias, or this resource does not exist, the alias is never pressed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InputAlias {
+    aliases: HashMap<AliasId, UserInput>,
+}
+
+impl InputAlias {
+    /// Registers `input` under the provided `id`, replacing any existing input
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CyclicAlias`] if `input` refers back to `id`, either directly or through other aliases,
+    /// including aliases nested inside of a [`UserInput::Preferred`] input.
+    /// In that case, the registry is not modified.
+    pub fn insert(&mut self, id: AliasId, input: impl Into<UserInput>) -> Result<(), CyclicAlias> {
+        let input = input.into();
+
+        if self.refers_to(&input, id) {
+            return Err(CyclicAlias);
+        }
+
+        self.aliases.insert(id, input);
+        Ok(())
+    }
+
+    /// Does the `input` refer to `id`, either directly or through the inputs that it contains?
+    ///
+    /// This always terminates, as [`insert`](Self::insert) never allows the registry to contain a cycle.
+    fn refers_to(&self, input: &UserInput, id: AliasId) -> bool {
+        match input {
+            UserInput::Alias(next_id) => {
+                *next_id == id
+                    || self
+                        .aliases
+                        .get(next_id)
+                        .map_or(false, |next| self.refers_to(next, id))
+            }
+            UserInput::Preferred(inputs) => inputs.iter().any(|input| self.refers_to(input, id)),
+            _ => false,
+        }
+    }
+
+    /// Fetches the [`UserInput`] registered under `id`, if any
+    #[must_use]
+    pub fn get(&self, id: AliasId) -> Option<&UserInput> {
+        self.aliases.get(&id)
+    }
+
+    /// Removes the [`UserInput`] registered under `id`, returning it if it existed
+    pub fn remove(&mut self, id: AliasId) -> Option<UserInput> {
+        self.aliases.remove(&id)
+    }
+}
@@ -0,0 +1,63 @@
+//! Named layers of a control scheme, such as on foot or in a vehicle, which can be switched on and off with [`ActiveContexts`].
+
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+
+/// A named layer of a modal control scheme
+///
+/// Inputs can be checked against a context using
+/// [`InputStreams::input_pressed_in_context`](crate::input_streams::InputStreams::input_pressed_in_context),
+/// which only matches while that context is in the [`ActiveContexts`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct InputContext(pub String);
+
+impl InputContext {
+    /// Creates a new [`InputContext`] with the provided `name`
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        InputContext(name.into())
+    }
+}
+
+impl From<&str> for InputContext {
+    fn from(name: &str) -> Self {
+        InputContext::new(name)
+    }
+}
+
+/// A resource that stores which [`InputContext`]s are currently active
+///
+/// Any number of contexts may be active at once.
+/// If this resource does not exist, no contexts are active.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ActiveContexts {
+    contexts: HashSet<InputContext>,
+}
+
+impl ActiveContexts {
+    /// Activates the `context`, returning `true` if it was not already active
+    pub fn activate(&mut self, context: impl Into<InputContext>) -> bool {
+        self.contexts.insert(context.into())
+    }
+
+    /// Deactivates the `context`, returning `true` if it was active
+    pub fn deactivate(&mut self, context: &InputContext) -> bool {
+        self.contexts.remove(context)
+    }
+
+    /// Is the `context` currently active?
+    #[must_use]
+    pub fn is_active(&self, context: &InputContext) -> bool {
+        self.contexts.contains(context)
+    }
+
+    /// Deactivates every context
+    pub fn clear(&mut self) {
+        self.contexts.clear();
+    }
+
+    /// Iterates over the active contexts, in an arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &InputContext> {
+        self.contexts.iter()
+    }
+}
@@ -0,0 +1,788 @@
+//! Tracks the state of buttons across frames, powering stateful [`UserInput`](crate::user_input::UserInput) variants.
+
+use crate::axislike::{AxisType, DualAxis, MouseMotionAxisType, MouseWheelAxisType, SingleAxis};
+use crate::user_input::{InputKind, UserInput};
+
+use bevy::input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::{KeyCode, ScanCode},
+    mouse::MouseButton,
+    touch::{Touch, Touches},
+    Axis, Input,
+};
+use bevy::math::Vec2;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
+use petitset::PetitSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+/// How long each [`AxisSample`] is stored in the [`InputHistory`] for
+///
+/// Gestures that use a longer window will only be able to see this far back.
+pub const MAX_SAMPLE_AGE: Duration = Duration::from_secs(5);
+
+/// How many frames of recorded input values are stored in the [`InputHistory`] for each input
+///
+/// Averages over more frames than this will only be able to see this far back.
+pub const MAX_VALUE_SAMPLES: usize = 120;
+
+/// The gamepad axes recorded in each [`AxisSample`]
+const SAMPLED_GAMEPAD_AXES: [GamepadAxisType; 6] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::LeftZ,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+    GamepadAxisType::RightZ,
+];
+
+/// The state of a single button, accumulated across frames
+///
+/// Records are created the first time that a button is pressed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ButtonRecord {
+    /// Has this button been pressed an odd number of times?
+    ///
+    /// This flips each time the button is pressed, and powers [`UserInput::Toggle`](crate::user_input::UserInput::Toggle).
+    pub toggled: bool,
+    /// When was this button pressed, if it is currently held?
+    pub pressed_at: Option<Instant>,
+    /// How long was this button held for, if it was released this frame?
+    ///
+    /// This powers [`UserInput::Charge`](crate::user_input::UserInput::Charge).
+    pub released_hold: Option<Duration>,
+    /// When was this button pressed, for each press within the last [`MAX_SAMPLE_AGE`]?
+    ///
+    /// This powers [`UserInput::Debounced`](crate::user_input::UserInput::Debounced)
+    /// and [`UserInput::DoubleTapHold`](crate::user_input::UserInput::DoubleTapHold).
+    pub recent_presses: VecDeque<Instant>,
+}
+
+impl ButtonRecord {
+    /// Updates the record to reflect that the button was just pressed
+    fn press(&mut self, current_instant: Instant) {
+        self.toggled = !self.toggled;
+        self.pressed_at = Some(current_instant);
+
+        self.recent_presses.push_back(current_instant);
+        while let Some(&oldest) = self.recent_presses.front() {
+            if current_instant.saturating_duration_since(oldest) > MAX_SAMPLE_AGE {
+                self.recent_presses.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Updates the record to reflect that the button was just released
+    fn release(&mut self, current_instant: Instant) {
+        if let Some(pressed_at) = self.pressed_at.take() {
+            self.released_hold = Some(current_instant.saturating_duration_since(pressed_at));
+        }
+    }
+}
+
+/// The values of the gamepad axes, and the mouse movement, on a single frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisSample {
+    /// When this sample was recorded
+    pub instant: Instant,
+    /// The value of each stick and trigger axis, for every connected gamepad
+    pub gamepad_axes: HashMap<GamepadAxis, f32>,
+    /// The total [`MouseMotion`](bevy::input::mouse::MouseMotion) during this frame
+    pub mouse_motion: Vec2,
+    /// The total [`MouseWheel`](bevy::input::mouse::MouseWheel) movement during this frame, in the units of each event
+    pub mouse_wheel: Vec2,
+}
+
+impl AxisSample {
+    /// The value of the `axis` on the provided `gamepad`, treating values in its dead zone as `0.0`
+    fn value(&self, axis: &SingleAxis, gamepad: Gamepad) -> Option<f32> {
+        let axis_type = match axis.axis_type {
+            AxisType::Gamepad(axis_type) => axis_type,
+            _ => return None,
+        };
+
+        let value = self
+            .gamepad_axes
+            .get(&GamepadAxis { gamepad, axis_type })
+            .copied()
+            .unwrap_or_default();
+
+        if value >= axis.negative_low && value <= axis.positive_low {
+            Some(0.0)
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// Mouse movement summed across several frames
+///
+/// This is useful when gameplay runs on a fixed timestep, which may run several times per frame, or not at all.
+/// Read from [`InputHistory::accumulated`] and [`InputHistory::drain_accumulated`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AccumulatedInput {
+    /// The total [`MouseMotion`](bevy::input::mouse::MouseMotion)
+    pub mouse_motion: Vec2,
+    /// The total [`MouseWheel`](bevy::input::mouse::MouseWheel) movement, in the units of each event
+    pub mouse_wheel: Vec2,
+}
+
+/// The state of a single gamepad, as recorded by the [`InputHistory`]
+///
+/// This is kept after the gamepad disconnects, to power the [`DisconnectGrace`](crate::gamepad_profiles::DisconnectGrace).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GamepadState {
+    /// The buttons that were pressed
+    pub pressed_buttons: HashSet<GamepadButtonType>,
+    /// The raw value of each sampled axis
+    pub axes: HashMap<GamepadAxisType, f32>,
+}
+
+/// A resource that stores the [`ButtonRecord`] of every button that has been pressed
+///
+/// This is updated each frame by [`update_input_history`](crate::systems::update_input_history),
+/// and is shared between all [`Actionlike`](crate::Actionlike) types.
+///
+/// The values of gamepad axes are also sampled each frame, and stored for up to [`MAX_SAMPLE_AGE`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InputHistory {
+    keycodes: HashMap<KeyCode, ButtonRecord>,
+    scan_codes: HashMap<ScanCode, ButtonRecord>,
+    mouse_buttons: HashMap<MouseButton, ButtonRecord>,
+    gamepad_buttons: HashMap<GamepadButton, ButtonRecord>,
+    axis_samples: VecDeque<AxisSample>,
+    /// The values recorded on the previous frame, keyed by the gamepads that were read
+    previous_values: HashMap<Vec<Gamepad>, HashMap<UserInput, f32>>,
+    /// The values recorded on the current frame, keyed by the gamepads that were read
+    current_values: HashMap<Vec<Gamepad>, HashMap<UserInput, f32>>,
+    /// The values recorded for each input on recent frames, from oldest to newest, keyed by the gamepads that were read
+    value_samples: HashMap<Vec<Gamepad>, HashMap<UserInput, VecDeque<f32>>>,
+    /// The distance between the first two active touches on the previous frame
+    previous_touch_distance: Option<f32>,
+    /// The distance between the first two active touches on the current frame
+    touch_distance: Option<f32>,
+    /// Has any gamepad been connected since this history was created?
+    gamepad_ever_connected: bool,
+    /// The state of each connected gamepad, as of the most recent update
+    gamepad_states: HashMap<Gamepad, GamepadState>,
+    /// The last-known state of each disconnected gamepad, and the instant at which it was found to be disconnected
+    disconnected_gamepads: HashMap<Gamepad, (Instant, GamepadState)>,
+    /// The mouse movement recorded since the last call to [`drain_accumulated`](Self::drain_accumulated)
+    accumulated: AccumulatedInput,
+    /// The instant passed to the most recent call to [`update`](Self::update)
+    last_update: Option<Instant>,
+}
+
+impl InputHistory {
+    /// Updates the stored records based on the buttons that were pressed and released this frame,
+    /// and samples the current value of each gamepad axis and the distance between the first two touches
+    ///
+    /// `current_instant` should be the time at which this frame began.
+    /// Gamepads in `disconnected` are treated as disconnected, even if they are still registered in `gamepads`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        keycode: &Input<KeyCode>,
+        scan_codes: &Input<ScanCode>,
+        mouse_button: &Input<MouseButton>,
+        gamepad_buttons: &Input<GamepadButton>,
+        gamepads: &Gamepads,
+        disconnected: &[Gamepad],
+        gamepad_axes: &Axis<GamepadAxis>,
+        touches: &Touches,
+        current_instant: Instant,
+    ) {
+        update_records(&mut self.keycodes, keycode, current_instant);
+        update_records(&mut self.scan_codes, scan_codes, current_instant);
+        update_records(&mut self.mouse_buttons, mouse_button, current_instant);
+        update_records(&mut self.gamepad_buttons, gamepad_buttons, current_instant);
+
+        // The values recorded during the last frame are now the previous values
+        self.previous_values = std::mem::take(&mut self.current_values);
+        self.previous_touch_distance = self.touch_distance;
+        self.touch_distance = touch_distance(touches);
+        self.gamepad_ever_connected |= gamepads.iter().next().is_some();
+
+        let mut sample = AxisSample {
+            instant: current_instant,
+            gamepad_axes: HashMap::default(),
+            mouse_motion: Vec2::ZERO,
+            mouse_wheel: Vec2::ZERO,
+        };
+        let mut gamepad_states = HashMap::default();
+        for &gamepad in gamepads
+            .iter()
+            .filter(|gamepad| !disconnected.contains(gamepad))
+        {
+            let mut gamepad_state = GamepadState::default();
+            for axis_type in SAMPLED_GAMEPAD_AXES {
+                let axis = GamepadAxis { gamepad, axis_type };
+                if let Some(value) = gamepad_axes.get(axis) {
+                    sample.gamepad_axes.insert(axis, value);
+                    gamepad_state.axes.insert(axis_type, value);
+                }
+            }
+            gamepad_state.pressed_buttons = gamepad_buttons
+                .get_pressed()
+                .filter(|gamepad_button| gamepad_button.gamepad == gamepad)
+                .map(|gamepad_button| gamepad_button.button_type)
+                .collect();
+
+            gamepad_states.insert(gamepad, gamepad_state);
+            self.disconnected_gamepads.remove(&gamepad);
+        }
+        self.axis_samples.push_back(sample);
+
+        // Gamepads that were connected last frame but are now missing keep their last-known state
+        for (gamepad, gamepad_state) in std::mem::take(&mut self.gamepad_states) {
+            if !gamepad_states.contains_key(&gamepad) {
+                self.disconnected_gamepads
+                    .insert(gamepad, (current_instant, gamepad_state));
+            }
+        }
+        self.gamepad_states = gamepad_states;
+
+        while let Some(oldest) = self.axis_samples.front() {
+            if current_instant.saturating_duration_since(oldest.instant) > MAX_SAMPLE_AGE {
+                self.axis_samples.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        self.last_update = Some(current_instant);
+    }
+
+    /// Records the total `mouse_motion` and `mouse_wheel` movement into the most recent [`AxisSample`],
+    /// and adds it to the [`AccumulatedInput`]
+    ///
+    /// This should be called after [`update`](Self::update) each frame.
+    pub fn record_mouse_movement(&mut self, mouse_motion: Vec2, mouse_wheel: Vec2) {
+        if let Some(sample) = self.axis_samples.back_mut() {
+            sample.mouse_motion = mouse_motion;
+            sample.mouse_wheel = mouse_wheel;
+        }
+
+        self.accumulated.mouse_motion += mouse_motion;
+        self.accumulated.mouse_wheel += mouse_wheel;
+    }
+
+    /// The mouse movement accumulated over every frame since the last call to [`drain_accumulated`](Self::drain_accumulated)
+    #[must_use]
+    pub fn accumulated(&self) -> AccumulatedInput {
+        self.accumulated
+    }
+
+    /// Returns the [`AccumulatedInput`], and resets it to zero
+    ///
+    /// Call this once per fixed timestep, so that movement from every frame is consumed exactly once,
+    /// no matter how many frames run for each step.
+    pub fn drain_accumulated(&mut self) -> AccumulatedInput {
+        std::mem::take(&mut self.accumulated)
+    }
+
+    /// Has any gamepad been connected at any point, as of the most recent update?
+    ///
+    /// Unlike checking the [`Gamepads`] resource, this stays `true` once the gamepad has been disconnected.
+    #[must_use]
+    pub fn gamepad_ever_connected(&self) -> bool {
+        self.gamepad_ever_connected
+    }
+
+    /// The last-known state of each gamepad that disconnected no more than `grace` before the most recent update
+    pub fn disconnected_gamepads(
+        &self,
+        grace: Duration,
+    ) -> impl Iterator<Item = (Gamepad, &GamepadState)> + '_ {
+        let last_update = self.last_update;
+
+        self.disconnected_gamepads
+            .iter()
+            .filter(move |(_, (disconnected_at, _))| match last_update {
+                Some(last_update) => {
+                    last_update.saturating_duration_since(*disconnected_at) <= grace
+                }
+                None => false,
+            })
+            .map(|(&gamepad, (_, gamepad_state))| (gamepad, gamepad_state))
+    }
+
+    /// Records the `value` of a bound `input` for this frame, as read from the `gamepads`
+    ///
+    /// This is called by [`record_input_values`](crate::systems::record_input_values) for every input in each [`InputMap`](crate::input_map::InputMap),
+    /// passing the [`gamepads_to_read`](crate::input_streams::InputStreams::gamepads_to_read) of that map.
+    /// Values are stored separately for each set of `gamepads`, so that players using different gamepads do not share them.
+    /// If the same input is recorded more than once in a frame for the same `gamepads`, the last value is kept.
+    pub fn record_value(&mut self, gamepads: &PetitSet<Gamepad, 8>, input: UserInput, value: f32) {
+        let key = gamepad_key(gamepads);
+        let samples = self
+            .value_samples
+            .entry(key.clone())
+            .or_default()
+            .entry(input.clone())
+            .or_default();
+        if self
+            .current_values
+            .entry(key)
+            .or_default()
+            .insert(input, value)
+            .is_some()
+        {
+            samples.pop_back();
+        }
+
+        samples.push_back(value);
+        if samples.len() > MAX_VALUE_SAMPLES {
+            samples.pop_front();
+        }
+    }
+
+    /// The mean of the values recorded for the `input` over the most recent `frames` frames, as read from the `gamepads`
+    ///
+    /// If fewer than `frames` values have been recorded, only those are averaged.
+    /// Returns [`None`] if no values have been recorded, or if `frames` is zero.
+    #[must_use]
+    pub fn averaged_value(
+        &self,
+        gamepads: &PetitSet<Gamepad, 8>,
+        input: &UserInput,
+        frames: usize,
+    ) -> Option<f32> {
+        let samples = self
+            .value_samples
+            .get(gamepad_key(gamepads).as_slice())?
+            .get(input)?;
+        let n_samples = frames.min(samples.len());
+        if n_samples == 0 {
+            return None;
+        }
+
+        let sum: f32 = samples.iter().rev().take(n_samples).sum();
+        Some(sum / n_samples as f32)
+    }
+
+    /// The value that the `input` had on the previous frame, as read from the `gamepads`, if it was recorded
+    #[must_use]
+    pub fn previous_value(
+        &self,
+        gamepads: &PetitSet<Gamepad, 8>,
+        input: &UserInput,
+    ) -> Option<f32> {
+        self.previous_values
+            .get(gamepad_key(gamepads).as_slice())?
+            .get(input)
+            .copied()
+    }
+
+    /// The [`AxisSample`]s recorded within `window` of the most recent update, from oldest to newest
+    pub fn axis_samples(&self, window: Duration) -> impl Iterator<Item = &AxisSample> {
+        let last_update = self.last_update;
+
+        self.axis_samples
+            .iter()
+            .filter(move |sample| match last_update {
+                Some(last_update) => {
+                    last_update.saturating_duration_since(sample.instant) <= window
+                }
+                None => false,
+            })
+    }
+
+    /// The total change in the value of every sampled gamepad axis between the two most recent [`AxisSample`]s
+    ///
+    /// Returns `0.0` if fewer than two samples have been recorded.
+    #[must_use]
+    pub fn gamepad_axis_delta(&self) -> f32 {
+        let mut samples = self.axis_samples.iter().rev();
+        let (latest, previous) = match (samples.next(), samples.next()) {
+            (Some(latest), Some(previous)) => (latest, previous),
+            _ => return 0.0,
+        };
+
+        latest
+            .gamepad_axes
+            .iter()
+            .map(|(axis, value)| {
+                let previous_value = previous.gamepad_axes.get(axis).copied().unwrap_or_default();
+                (value - previous_value).abs()
+            })
+            .sum()
+    }
+
+    /// The total signed angle in radians swept by the `axis` on the provided `gamepad` within `window`
+    ///
+    /// Counterclockwise motion is positive.
+    /// Samples where the stick is inside of its dead zone are skipped.
+    /// Only gamepad axes are sampled: returns `0.0` for other axis types.
+    #[must_use]
+    pub fn swept_angle(&self, axis: &DualAxis, gamepad: Gamepad, window: Duration) -> f32 {
+        let mut swept_angle = 0.0;
+        let mut previous: Option<Vec2> = None;
+
+        for sample in self.axis_samples(window) {
+            let xy = match (
+                sample.value(&axis.x, gamepad),
+                sample.value(&axis.y, gamepad),
+            ) {
+                (Some(x), Some(y)) => Vec2::new(x, y),
+                _ => return 0.0,
+            };
+
+            if xy == Vec2::ZERO {
+                continue;
+            }
+
+            if let Some(previous) = previous {
+                swept_angle += previous.angle_between(xy);
+            }
+            previous = Some(xy);
+        }
+
+        swept_angle
+    }
+
+    /// How many times the `axis` changed direction within `window`
+    ///
+    /// Gamepad axes are read from the provided `gamepad`, and their velocity is the change in value between samples.
+    /// Mouse axes already measure movement, so their sampled values are used as the velocity.
+    /// Frames without any movement are skipped. Returns `0` for gamepad axes if no `gamepad` is provided.
+    /// This powers [`UserInput::Shake`](crate::user_input::UserInput::Shake).
+    #[must_use]
+    pub fn axis_reversals(
+        &self,
+        axis: AxisType,
+        gamepad: Option<Gamepad>,
+        window: Duration,
+    ) -> u32 {
+        let velocities: Vec<f32> = match axis {
+            AxisType::Gamepad(axis_type) => {
+                let gamepad = match gamepad {
+                    Some(gamepad) => gamepad,
+                    None => return 0,
+                };
+                let gamepad_axis = GamepadAxis { gamepad, axis_type };
+
+                let values: Vec<f32> = self
+                    .axis_samples(window)
+                    .map(|sample| {
+                        sample
+                            .gamepad_axes
+                            .get(&gamepad_axis)
+                            .copied()
+                            .unwrap_or_default()
+                    })
+                    .collect();
+
+                values.windows(2).map(|pair| pair[1] - pair[0]).collect()
+            }
+            AxisType::MouseMotion(axis_type) => self
+                .axis_samples(window)
+                .map(|sample| match axis_type {
+                    MouseMotionAxisType::X => sample.mouse_motion.x,
+                    MouseMotionAxisType::Y => sample.mouse_motion.y,
+                })
+                .collect(),
+            AxisType::MouseWheel(axis_type) => self
+                .axis_samples(window)
+                .map(|sample| match axis_type {
+                    MouseWheelAxisType::X => sample.mouse_wheel.x,
+                    MouseWheelAxisType::Y => sample.mouse_wheel.y,
+                })
+                .collect(),
+        };
+
+        let mut reversals = 0;
+        let mut previous_direction: Option<bool> = None;
+        for velocity in velocities {
+            if velocity == 0.0 {
+                continue;
+            }
+
+            let direction = velocity > 0.0;
+            if previous_direction.map_or(false, |previous| previous != direction) {
+                reversals += 1;
+            }
+            previous_direction = Some(direction);
+        }
+
+        reversals
+    }
+
+    /// How much has the distance between the first two active touches changed since the previous frame?
+    ///
+    /// Touches are ordered by their id. Spreading the touches apart is positive.
+    /// Returns `0.0` unless there were at least two touches on both this frame and the previous frame.
+    /// This powers [`UserInput::Pinch`](crate::user_input::UserInput::Pinch).
+    #[must_use]
+    pub fn pinch_delta(&self) -> f32 {
+        match (self.previous_touch_distance, self.touch_distance) {
+            (Some(previous_distance), Some(distance)) => distance - previous_distance,
+            _ => 0.0,
+        }
+    }
+
+    /// Fetches the [`ButtonRecord`] for the provided `button`, if it has ever been pressed
+    ///
+    /// Gamepad buttons are looked up on the provided `gamepad`.
+    /// Inputs that are not buttons (such as axes) are never recorded.
+    #[must_use]
+    pub fn record(&self, button: InputKind, gamepad: Option<Gamepad>) -> Option<&ButtonRecord> {
+        match button {
+            InputKind::Keyboard(keycode) => self.keycodes.get(&keycode),
+            InputKind::KeyLocation(scan_code) => self.scan_codes.get(&scan_code),
+            InputKind::Mouse(mouse_button) => self.mouse_buttons.get(&mouse_button),
+            InputKind::GamepadButton(button_type) => {
+                let gamepad = gamepad?;
+                self.gamepad_buttons.get(&GamepadButton {
+                    gamepad,
+                    button_type,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// How many of the `steps` of a [`UserInput::Staircase`](crate::user_input::UserInput::Staircase) have been completed?
+    ///
+    /// Steps are counted from the start, for as long as each step is held and was pressed after the step before it.
+    /// Returns `0` if the first step is not held.
+    #[must_use]
+    pub fn staircase_progress(&self, steps: &[InputKind], gamepad: Option<Gamepad>) -> usize {
+        let mut previous_press: Option<Instant> = None;
+
+        for (progress, step) in steps.iter().enumerate() {
+            let pressed_at = match self
+                .record(*step, gamepad)
+                .and_then(|record| record.pressed_at)
+            {
+                Some(pressed_at) => pressed_at,
+                None => return progress,
+            };
+
+            match previous_press {
+                Some(previous_press) if pressed_at <= previous_press => return progress,
+                _ => previous_press = Some(pressed_at),
+            }
+        }
+
+        steps.len()
+    }
+
+    /// Is the `button` currently toggled on?
+    #[must_use]
+    pub fn toggled(&self, button: InputKind, gamepad: Option<Gamepad>) -> bool {
+        self.record(button, gamepad)
+            .map(|record| record.toggled)
+            .unwrap_or_default()
+    }
+
+    /// How long has the `button` been held for, as of the most recent update?
+    ///
+    /// Returns [`None`] if the button is not currently held.
+    #[must_use]
+    pub fn held_duration(&self, button: InputKind, gamepad: Option<Gamepad>) -> Option<Duration> {
+        let pressed_at = self.record(button, gamepad)?.pressed_at?;
+        let last_update = self.last_update?;
+
+        Some(last_update.saturating_duration_since(pressed_at))
+    }
+
+    /// How charged was the `button` when it was released this frame?
+    ///
+    /// The charge is the duration that the button was held for divided by `max`, clamped to `[0.0, 1.0]`.
+    /// Returns [`None`] if the button was not released this frame.
+    #[must_use]
+    pub fn charge(
+        &self,
+        button: InputKind,
+        gamepad: Option<Gamepad>,
+        max: Duration,
+    ) -> Option<f32> {
+        let held = self.record(button, gamepad)?.released_hold?;
+
+        if max.is_zero() {
+            return Some(1.0);
+        }
+
+        Some((held.as_secs_f32() / max.as_secs_f32()).clamp(0.0, 1.0))
+    }
+
+    /// Is the `button` held, and did its current press begin at least `cooldown` after the last press that was accepted?
+    ///
+    /// Presses within the `cooldown` of an accepted press are ignored, and do not restart the cooldown.
+    /// Only presses within the last [`MAX_SAMPLE_AGE`] are considered.
+    /// Returns `false` for inputs that are never recorded, such as axes.
+    #[must_use]
+    pub fn debounced(
+        &self,
+        button: InputKind,
+        gamepad: Option<Gamepad>,
+        cooldown: Duration,
+    ) -> bool {
+        let record = match self.record(button, gamepad) {
+            Some(record) => record,
+            None => return false,
+        };
+        let pressed_at = match record.pressed_at {
+            Some(pressed_at) => pressed_at,
+            None => return false,
+        };
+
+        let mut last_accepted: Option<Instant> = None;
+        for &press in record.recent_presses.iter() {
+            let accepted = match last_accepted {
+                Some(last_accepted) => press.saturating_duration_since(last_accepted) >= cooldown,
+                None => true,
+            };
+
+            if accepted {
+                last_accepted = Some(press);
+            }
+        }
+
+        last_accepted == Some(pressed_at)
+    }
+
+    /// Is the `button` being held after a double tap?
+    ///
+    /// This is `true` while the button is held, if the current press began within `tap_window` of the press before it.
+    /// Returns `false` for inputs that are never recorded, such as axes.
+    #[must_use]
+    pub fn double_tap_held(
+        &self,
+        button: InputKind,
+        gamepad: Option<Gamepad>,
+        tap_window: Duration,
+    ) -> bool {
+        let record = match self.record(button, gamepad) {
+            Some(record) => record,
+            None => return false,
+        };
+        let pressed_at = match record.pressed_at {
+            Some(pressed_at) => pressed_at,
+            None => return false,
+        };
+
+        let mut presses = record.recent_presses.iter().rev();
+        match (presses.next(), presses.next()) {
+            (Some(&latest_press), Some(&first_tap)) if latest_press == pressed_at => {
+                pressed_at.saturating_duration_since(first_tap) <= tap_window
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A resource that measures how many [`MouseMotion`](bevy::input::mouse::MouseMotion) events are received per second
+///
+/// This is updated each frame by [`update_mouse_sample_rate`](crate::systems::update_mouse_sample_rate),
+/// and averages the event counts over its [`window`](Self::window).
+/// This is useful for detecting mice with a low polling rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MouseSampleRate {
+    window: Duration,
+    /// The instant of each recorded frame, and the number of events received during it
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl MouseSampleRate {
+    /// The default length of time that event counts are averaged over
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+    /// Creates a new [`MouseSampleRate`], which averages event counts over the provided `window`
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        MouseSampleRate {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// The length of time that event counts are averaged over
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Records that `events` mouse motion events were received during the frame ending at `current_instant`
+    ///
+    /// Frames older than the [`window`](Self::window) are discarded.
+    pub fn record(&mut self, current_instant: Instant, events: usize) {
+        self.samples.push_back((current_instant, events));
+
+        while let Some(&(instant, _)) = self.samples.front() {
+            if current_instant.saturating_duration_since(instant) > self.window {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// The average number of mouse motion events received per second over the [`window`](Self::window)
+    ///
+    /// The events of the oldest recorded frame are not counted, as they arrived before the measured span began.
+    /// Returns `0.0` until at least two frames with distinct instants have been recorded.
+    #[must_use]
+    pub fn events_per_second(&self) -> f32 {
+        let (first, last) = match (self.samples.front(), self.samples.back()) {
+            (Some(&(first, _)), Some(&(last, _))) => (first, last),
+            _ => return 0.0,
+        };
+
+        let span = last.saturating_duration_since(first).as_secs_f32();
+        if span == 0.0 {
+            return 0.0;
+        }
+
+        let events: usize = self.samples.iter().skip(1).map(|&(_, events)| events).sum();
+        events as f32 / span
+    }
+}
+
+impl Default for MouseSampleRate {
+    fn default() -> Self {
+        MouseSampleRate::new(MouseSampleRate::DEFAULT_WINDOW)
+    }
+}
+
+/// The `gamepads` sorted by id, so that the same set of gamepads always produces the same key
+fn gamepad_key(gamepads: &PetitSet<Gamepad, 8>) -> Vec<Gamepad> {
+    let mut key: Vec<Gamepad> = gamepads.iter().copied().collect();
+    key.sort_by_key(|gamepad| gamepad.id);
+    key
+}
+
+/// The distance between the first two active touches, ordered by their id
+fn touch_distance(touches: &Touches) -> Option<f32> {
+    let mut touches: Vec<&Touch> = touches.iter().collect();
+    touches.sort_by_key(|touch| touch.id());
+
+    match touches.as_slice() {
+        [first, second, ..] => Some(first.position().distance(second.position())),
+        _ => None,
+    }
+}
+
+fn update_records<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    records: &mut HashMap<T, ButtonRecord>,
+    input: &Input<T>,
+    current_instant: Instant,
+) {
+    // Completed holds are only reported for a single frame
+    for record in records.values_mut() {
+        record.released_hold = None;
+    }
+
+    for &button in input.get_just_released() {
+        if let Some(record) = records.get_mut(&button) {
+            record.release(current_instant);
+        }
+    }
+
+    for &button in input.get_just_pressed() {
+        records.entry(button).or_default().press(current_instant);
+    }
+}
@@ -0,0 +1,60 @@
+//! Callbacks that are run whenever a particular [`UserInput`] matches, stored in the [`InputMatchHooks`] resource.
+
+use crate::user_input::UserInput;
+
+use bevy::utils::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A callback that is passed the matching [`UserInput`] and its current value
+pub type InputMatchHook = Arc<dyn Fn(&UserInput, f32) + Send + Sync>;
+
+/// A resource that maps [`UserInput`]s to callbacks, which are run whenever that input matches
+///
+/// Hooks are run by [`InputStreams::input_pressed`](crate::input_streams::InputStreams::input_pressed) when the input is pressed,
+/// and by [`InputStreams::input_value`](crate::input_streams::InputStreams::input_value) when its value is non-zero.
+/// The [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) runs each hook at most once per frame for each [`InputMap`](crate::input_map::InputMap) that binds the input:
+/// internal passes, such as clash resolution, history recording and the members of composite inputs, never run hooks.
+/// Calling these methods yourself runs the hook again.
+///
+/// This is intended for debugging which bindings are firing, and is never serialized.
+#[derive(Default, Clone)]
+pub struct InputMatchHooks {
+    hooks: HashMap<UserInput, InputMatchHook>,
+}
+
+impl InputMatchHooks {
+    /// Registers the `hook` for the provided `input`, replacing any existing hook
+    pub fn insert(
+        &mut self,
+        input: impl Into<UserInput>,
+        hook: impl Fn(&UserInput, f32) + Send + Sync + 'static,
+    ) {
+        self.hooks.insert(input.into(), Arc::new(hook));
+    }
+
+    /// Fetches the [`InputMatchHook`] registered for the `input`, if any
+    #[must_use]
+    pub fn get(&self, input: &UserInput) -> Option<&InputMatchHook> {
+        self.hooks.get(input)
+    }
+
+    /// Removes the [`InputMatchHook`] registered for the `input`, returning it if it existed
+    pub fn remove(&mut self, input: &UserInput) -> Option<InputMatchHook> {
+        self.hooks.remove(input)
+    }
+
+    /// Is no hook registered?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+impl Debug for InputMatchHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputMatchHooks")
+            .field("inputs", &self.hooks.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,60 @@
+//! Recorded sequences of input values, which can be compared against live input for macro playback and recording tools.
+
+use crate::input_streams::InputStreams;
+use crate::user_input::UserInput;
+
+use serde::{Deserialize, Serialize};
+
+/// The values of a set of inputs on a single frame
+///
+/// Compare a recorded frame against live input using [`InputStreams::matches_step`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputFrame {
+    /// The recorded [`input_value`](InputStreams::input_value) of each input
+    pub values: Vec<(UserInput, f32)>,
+}
+
+impl InputFrame {
+    /// Records the current value of each of the `inputs` from the `input_streams`
+    #[must_use]
+    pub fn capture(
+        input_streams: &InputStreams,
+        inputs: impl IntoIterator<Item = UserInput>,
+    ) -> Self {
+        InputFrame {
+            values: inputs
+                .into_iter()
+                .map(|input| {
+                    let value = input_streams.input_value(&input);
+                    (input, value)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A recorded sequence of [`InputFrame`]s, played back one step at a time
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Macro {
+    /// The recorded frames, in the order that they should be played back
+    pub steps: Vec<InputFrame>,
+}
+
+impl Macro {
+    /// Adds the `step` to the end of the macro
+    pub fn push(&mut self, step: InputFrame) {
+        self.steps.push(step);
+    }
+
+    /// The number of steps in the macro
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Does the macro contain no steps?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
@@ -80,6 +80,9 @@ pub struct InputMap<A: Actionlike> {
     /// indexed by the `Actionlike::id` of `A`
     map: Vec<PetitSet<UserInput, 16>>,
     associated_gamepad: Option<Gamepad>,
+    associated_gamepads: PetitSet<Gamepad, 8>,
+    key_resolution: KeyResolution,
+    external_focus_inputs: PetitSet<InputKind, 16>,
     #[serde(skip)]
     marker: PhantomData<A>,
 }
@@ -89,11 +92,56 @@ impl<A: Actionlike> Default for InputMap<A> {
         InputMap {
             map: A::variants().map(|_| PetitSet::default()).collect(),
             associated_gamepad: None,
+            associated_gamepads: PetitSet::default(),
+            key_resolution: KeyResolution::default(),
+            external_focus_inputs: PetitSet::default(),
             marker: PhantomData,
         }
     }
 }
 
+/// How an [`InputMap`] resolves actions that are bound to both [`KeyCode`](bevy::input::keyboard::KeyCode)s and [`ScanCode`](bevy::input::keyboard::ScanCode)s
+///
+/// On keyboard layouts other than QWERTY, a [`KeyCode`](bevy::input::keyboard::KeyCode) and a [`ScanCode`](bevy::input::keyboard::ScanCode)
+/// bound to the same action may refer to different physical keys.
+/// This policy is only consulted for actions that have bindings of both kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyResolution {
+    /// Ignore any bindings that use [`InputKind::KeyLocation`], following the logical keyboard layout
+    PreferKeyCode,
+    /// Ignore any bindings that use [`InputKind::Keyboard`], following the physical key locations
+    PreferScanCode,
+    /// Use all bindings, so either kind of key can press the action
+    Either,
+}
+
+impl Default for KeyResolution {
+    fn default() -> Self {
+        KeyResolution::Either
+    }
+}
+
+impl KeyResolution {
+    /// Should the `input` be ignored, given all of the `bindings` for its action?
+    fn ignores(self, input: &UserInput, bindings: &PetitSet<UserInput, 16>) -> bool {
+        match self {
+            KeyResolution::Either => false,
+            KeyResolution::PreferKeyCode => {
+                !input.raw_inputs().scan_codes.is_empty()
+                    && bindings
+                        .iter()
+                        .any(|binding| !binding.raw_inputs().keycodes.is_empty())
+            }
+            KeyResolution::PreferScanCode => {
+                !input.raw_inputs().keycodes.is_empty()
+                    && bindings
+                        .iter()
+                        .any(|binding| !binding.raw_inputs().scan_codes.is_empty())
+            }
+        }
+    }
+}
+
 // Constructors
 impl<A: Actionlike> InputMap<A> {
     /// Creates a new [`InputMap`] from an iterator of `(user_input, action)` pairs
@@ -231,7 +279,9 @@ impl<A: Actionlike> InputMap<A> {
     /// This adds both of their bindings to the resulting [`InputMap`].
     /// Like usual, any duplicate bindings are ignored.
     ///
-    /// If the associated gamepads do not match, the resulting associated gamepad will be set to `None`.
+    /// If the associated gamepads do not match, the resulting associated gamepad will be set to `None`,
+    /// and likewise the resulting set of [`gamepads`](Self::gamepads) will be empty.
+    /// The [`KeyResolution`] of the [`InputMap`] this method was called on is kept.
     pub fn merge(&mut self, other: &InputMap<A>) -> &mut Self {
         let associated_gamepad = if self.associated_gamepad == other.associated_gamepad {
             self.associated_gamepad
@@ -239,11 +289,27 @@ impl<A: Actionlike> InputMap<A> {
             None
         };
 
+        let associated_gamepads = if self.associated_gamepads == other.associated_gamepads {
+            self.associated_gamepads.clone()
+        } else {
+            PetitSet::default()
+        };
+
         let mut new_map = InputMap {
             associated_gamepad,
+            associated_gamepads,
+            key_resolution: self.key_resolution,
             ..Default::default()
         };
 
+        for &input in self
+            .external_focus_inputs
+            .iter()
+            .chain(other.external_focus_inputs.iter())
+        {
+            new_map.external_focus_inputs.insert(input);
+        }
+
         for action in A::variants() {
             for input in self.get(action.clone()).iter() {
                 new_map.insert(input.clone(), action.clone());
@@ -287,6 +353,66 @@ impl<A: Actionlike> InputMap<A> {
         self.associated_gamepad = None;
         self
     }
+
+    /// Fetches the set of [`Gamepad`]s that are all treated as the entity controlled by this input map
+    ///
+    /// If this is not empty, it is used instead of the [`gamepad`](Self::gamepad).
+    /// See [`InputStreams::associated_gamepads`] for details.
+    #[must_use]
+    pub fn gamepads(&self) -> &PetitSet<Gamepad, 8> {
+        &self.associated_gamepads
+    }
+
+    /// Adds the `gamepad` to the set of [`Gamepad`]s that are all treated as the entity controlled by this input map
+    ///
+    /// This is useful for local co-op setups where several controllers steer the same character.
+    pub fn add_gamepad(&mut self, gamepad: Gamepad) -> &mut Self {
+        self.associated_gamepads.insert(gamepad);
+        self
+    }
+
+    /// Clears the set of [`Gamepad`]s added by [`add_gamepad`](Self::add_gamepad)
+    pub fn clear_gamepads(&mut self) -> &mut Self {
+        self.associated_gamepads = PetitSet::default();
+        self
+    }
+
+    /// Fetches the [`KeyResolution`] used when an action is bound to both [`KeyCode`](bevy::input::keyboard::KeyCode)s and [`ScanCode`](bevy::input::keyboard::ScanCode)s
+    #[must_use]
+    pub fn key_resolution(&self) -> KeyResolution {
+        self.key_resolution
+    }
+
+    /// Sets the [`KeyResolution`] used when an action is bound to both [`KeyCode`](bevy::input::keyboard::KeyCode)s and [`ScanCode`](bevy::input::keyboard::ScanCode)s
+    pub fn set_key_resolution(&mut self, key_resolution: KeyResolution) -> &mut Self {
+        self.key_resolution = key_resolution;
+        self
+    }
+
+    /// Fetches the inputs that are ignored while the [`ExternalFocus`](crate::plugin::ExternalFocus) resource is `true`
+    #[must_use]
+    pub fn external_focus_inputs(&self) -> &PetitSet<InputKind, 16> {
+        &self.external_focus_inputs
+    }
+
+    /// Controls whether the `input` is ignored while the [`ExternalFocus`](crate::plugin::ExternalFocus) resource is `true`
+    ///
+    /// By default, inputs are pressed regardless of external focus.
+    /// This applies to every action bound to `input`, including through chords and virtual dpads.
+    pub fn set_respect_external_focus(
+        &mut self,
+        input: impl Into<InputKind>,
+        respect: bool,
+    ) -> &mut Self {
+        let input = input.into();
+
+        if respect {
+            self.external_focus_inputs.insert(input);
+        } else {
+            self.external_focus_inputs.remove(&input);
+        }
+        self
+    }
 }
 
 // Check whether buttons are pressed
@@ -321,8 +447,13 @@ impl<A: Actionlike> InputMap<A> {
         // Generate the raw action presses
         for action in A::variants() {
             let mut inputs = Vec::new();
+            let bindings = self.get(action.clone());
+
+            for input in bindings.iter() {
+                if self.key_resolution.ignores(input, bindings) {
+                    continue;
+                }
 
-            for input in self.get(action.clone()).iter() {
                 let action = &mut action_data[action.index()];
 
                 // Merge axis pair into action data
@@ -338,7 +469,8 @@ impl<A: Actionlike> InputMap<A> {
                 if input_streams.input_pressed(input) {
                     inputs.push(input.clone());
 
-                    action.value += input_streams.input_value(input);
+                    // The hook has already been run by `input_pressed`
+                    action.value += input_streams.input_value_without_hooks(input);
                 }
             }
 
@@ -23,7 +23,7 @@ use bevy::input::mouse::MouseScrollUnit;
 use bevy::input::ButtonState;
 use bevy::input::{
     gamepad::{Gamepad, GamepadButton, GamepadEvent, GamepadEventType},
-    keyboard::{KeyCode, KeyboardInput},
+    keyboard::{KeyCode, KeyboardInput, ScanCode},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
     touch::{TouchInput, Touches},
     Input,
@@ -161,6 +161,15 @@ impl MockInput for MutableInputStreams<'_> {
             });
         }
 
+        // Physical keyboard locations
+        for scan_code in raw_inputs.scan_codes {
+            self.keyboard_events.send(KeyboardInput {
+                scan_code: scan_code.0,
+                key_code: None,
+                state: ButtonState::Pressed,
+            });
+        }
+
         // Mouse buttons
         for button in raw_inputs.mouse_buttons {
             self.mouse_button_events.send(MouseButtonInput {
@@ -296,6 +305,14 @@ impl MockInput for MutableInputStreams<'_> {
             });
         }
 
+        for scan_code in raw_inputs.scan_codes {
+            self.keyboard_events.send(KeyboardInput {
+                scan_code: scan_code.0,
+                key_code: None,
+                state: ButtonState::Released,
+            });
+        }
+
         for button in raw_inputs.mouse_buttons {
             self.mouse_button_events.send(MouseButtonInput {
                 button,
@@ -323,9 +340,11 @@ impl MockInput for MutableInputStreams<'_> {
         *self.gamepad_buttons = Default::default();
         *self.gamepad_axes = Default::default();
         *self.keycode = Default::default();
+        *self.scan_codes = Default::default();
         *self.mouse_button = Default::default();
         *self.mouse_wheel = Default::default();
         *self.mouse_motion = Default::default();
+        *self.touches = Default::default();
     }
 
     #[cfg(feature = "ui")]
@@ -389,10 +408,12 @@ impl MockInput for World {
         let mut input_system_state: SystemState<(
             Option<ResMut<Input<GamepadButton>>>,
             Option<ResMut<Input<KeyCode>>>,
+            Option<ResMut<Input<ScanCode>>>,
             Option<ResMut<Input<MouseButton>>>,
         )> = SystemState::new(self);
 
-        let (maybe_gamepad, maybe_keyboard, maybe_mouse) = input_system_state.get_mut(self);
+        let (maybe_gamepad, maybe_keyboard, maybe_scan_codes, maybe_mouse) =
+            input_system_state.get_mut(self);
 
         if let Some(mut gamepad) = maybe_gamepad {
             *gamepad = Default::default();
@@ -402,6 +423,10 @@ impl MockInput for World {
             *keyboard = Default::default();
         }
 
+        if let Some(mut scan_codes) = maybe_scan_codes {
+            *scan_codes = Default::default();
+        }
+
         if let Some(mut mouse) = maybe_mouse {
             *mouse = Default::default();
         }
@@ -0,0 +1,180 @@
+//! Owned copies of the input state at a particular moment, which can be evaluated later.
+
+use crate::input_streams::{
+    DigitalAnalogMode, InputStreams, KeyboardStateSource, MouseMotionMode, RemoteInput,
+    ScrollSourcePriority, GAMEPAD_AXIS_TYPES, GAMEPAD_BUTTON_ORDER,
+};
+use crate::user_input::UserInput;
+
+use bevy::ecs::event::Events;
+use bevy::ecs::system::{IntoSystem, System};
+use bevy::ecs::world::World;
+use bevy::input::{
+    gamepad::{
+        gamepad_connection_system, Gamepad, GamepadAxis, GamepadButton, GamepadEvent,
+        GamepadEventType, Gamepads,
+    },
+    keyboard::{KeyCode, KeyboardInput, ScanCode},
+    mouse::{MouseButton, MouseMotion, MouseWheel},
+    touch::Touches,
+    Axis, Input,
+};
+use petitset::PetitSet;
+
+/// An owned copy of the button and axis state read by an [`InputStreams`]
+///
+/// Unlike [`InputStreams`], this does not borrow from the [`World`](bevy::ecs::world::World),
+/// and so can be stored and evaluated later, such as for lag compensation.
+///
+/// Only the state of buttons and of the standard gamepad axes is captured:
+/// event-based inputs such as mouse motion and the mouse wheel, touches,
+/// and stateful inputs that depend on the [`InputHistory`](crate::input_history::InputHistory) are never pressed in a snapshot.
+/// Gamepad axes are stored as their raw values, without any [`GamepadProfiles`](crate::gamepad_profiles::GamepadProfiles) applied.
+#[derive(Debug)]
+pub struct OwnedInputSnapshot {
+    gamepad_buttons: Input<GamepadButton>,
+    gamepad_button_axes: Axis<GamepadButton>,
+    gamepad_axes: Axis<GamepadAxis>,
+    gamepads: Gamepads,
+    keycode: Input<KeyCode>,
+    scan_codes: Input<ScanCode>,
+    keyboard_events: Events<KeyboardInput>,
+    mouse_button: Option<Input<MouseButton>>,
+    mouse_wheel: Events<MouseWheel>,
+    mouse_motion: Events<MouseMotion>,
+    touches: Touches,
+    associated_gamepads: PetitSet<Gamepad, 8>,
+    text_input_active: bool,
+    digital_analog_mode: DigitalAnalogMode,
+    allow_guide_button: bool,
+}
+
+impl OwnedInputSnapshot {
+    /// Captures the current state of the `input_streams`
+    ///
+    /// Gamepad inputs are captured for the [`gamepads_to_read`](InputStreams::gamepads_to_read) only.
+    #[must_use]
+    pub fn capture(input_streams: &InputStreams) -> Self {
+        let associated_gamepads = input_streams.gamepads_to_read();
+
+        let mut gamepad_button_axes = Axis::<GamepadButton>::default();
+        let mut gamepad_axes = Axis::<GamepadAxis>::default();
+        for &gamepad in associated_gamepads.iter() {
+            for button_type in GAMEPAD_BUTTON_ORDER {
+                let gamepad_button = GamepadButton {
+                    gamepad,
+                    button_type,
+                };
+
+                if let Some(value) = input_streams.gamepad_button_axes.get(gamepad_button) {
+                    gamepad_button_axes.set(gamepad_button, value);
+                }
+            }
+
+            for axis_type in GAMEPAD_AXIS_TYPES {
+                let gamepad_axis = GamepadAxis { gamepad, axis_type };
+
+                if let Some(value) = input_streams.gamepad_axes.get(gamepad_axis) {
+                    gamepad_axes.set(gamepad_axis, value);
+                }
+            }
+        }
+
+        OwnedInputSnapshot {
+            gamepad_buttons: input_streams.gamepad_buttons.clone(),
+            gamepad_button_axes,
+            gamepad_axes,
+            gamepads: copy_gamepads(input_streams.gamepads),
+            keycode: input_streams.keycode.clone(),
+            scan_codes: input_streams.scan_codes.clone(),
+            keyboard_events: Events::default(),
+            mouse_button: input_streams.mouse_button.cloned(),
+            mouse_wheel: Events::default(),
+            mouse_motion: Events::default(),
+            touches: Touches::default(),
+            associated_gamepads,
+            text_input_active: input_streams.text_input_active,
+            digital_analog_mode: input_streams.digital_analog_mode,
+            allow_guide_button: input_streams.allow_guide_button,
+        }
+    }
+
+    /// An [`InputStreams`] that reads from this snapshot
+    #[must_use]
+    pub fn input_streams(&self) -> InputStreams<'_> {
+        InputStreams {
+            gamepad_buttons: &self.gamepad_buttons,
+            gamepad_button_axes: &self.gamepad_button_axes,
+            gamepad_axes: &self.gamepad_axes,
+            gamepads: &self.gamepads,
+            keycode: &self.keycode,
+            scan_codes: &self.scan_codes,
+            keyboard_events: &self.keyboard_events,
+            mouse_button: self.mouse_button.as_ref(),
+            mouse_wheel: &self.mouse_wheel,
+            mouse_motion: &self.mouse_motion,
+            touches: &self.touches,
+            input_history: None,
+            input_aliases: None,
+            input_match_hooks: None,
+            mouse_sample_rate: None,
+            gamepad_profiles: None,
+            gamepad_calibration: None,
+            gamepad_button_remap: None,
+            disconnect_grace: None,
+            active_contexts: None,
+            analog_keyboard: None,
+            text_input_active: self.text_input_active,
+            external_focus: false,
+            associated_gamepad: None,
+            associated_gamepads: self.associated_gamepads.clone(),
+            max_delta_per_event: None,
+            scroll_threshold: 0.0,
+            scroll_source_priority: ScrollSourcePriority::Sum,
+            max_scroll_per_frame: None,
+            pixels_per_line: None,
+            value_warning_bound: 1.0,
+            digital_analog_mode: self.digital_analog_mode,
+            keyboard_state_source: KeyboardStateSource::Polled,
+            external_focus_inputs: PetitSet::default(),
+            remote_input: RemoteInput(false),
+            mouse_motion_mode: MouseMotionMode::Relative,
+            cursor_grabbed: true,
+            cursor_position: None,
+            allow_guide_button: self.allow_guide_button,
+        }
+    }
+}
+
+/// An owned copy of the registered `gamepads`
+///
+/// [`Gamepads`] cannot be cloned or registered directly, so the connection events are replayed
+/// through bevy's [`gamepad_connection_system`] on a scratch [`World`].
+fn copy_gamepads(gamepads: &Gamepads) -> Gamepads {
+    let mut world = World::new();
+    world.init_resource::<Gamepads>();
+
+    let mut gamepad_events = Events::<GamepadEvent>::default();
+    for &gamepad in gamepads.iter() {
+        gamepad_events.send(GamepadEvent {
+            gamepad,
+            event_type: GamepadEventType::Connected,
+        });
+    }
+    world.insert_resource(gamepad_events);
+
+    let mut connection_system = IntoSystem::into_system(gamepad_connection_system);
+    connection_system.initialize(&mut world);
+    connection_system.run((), &mut world);
+
+    world.remove_resource::<Gamepads>().unwrap_or_default()
+}
+
+/// Was the `input` pressed when the `snapshot` was captured?
+///
+/// This uses the same matching logic as [`InputStreams::input_pressed`].
+/// Use [`OwnedInputSnapshot::input_streams`] to make any other query against the snapshot.
+#[must_use]
+pub fn input_pressed_in(snapshot: &OwnedInputSnapshot, input: &UserInput) -> bool {
+    snapshot.input_streams().input_pressed(input)
+}
@@ -1,25 +1,49 @@
 //! Unified input streams for working with [`bevy::input`] data.
 
 use bevy::input::{
-    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEventRaw, Gamepads},
-    keyboard::{KeyCode, KeyboardInput},
+    gamepad::{
+        Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEventRaw,
+        Gamepads,
+    },
+    keyboard::{KeyCode, KeyboardInput, ScanCode},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
-    Axis, Input,
+    touch::{Touch, Touches},
+    Axis, ButtonState, Input,
 };
 use petitset::PetitSet;
 
-use bevy::ecs::prelude::{Events, ResMut, World};
+use bevy::ecs::prelude::{Component, Events, Res, ResMut, World};
 use bevy::ecs::system::SystemState;
+use bevy::math::{IVec2, Vec2};
+use bevy::utils::{Duration, HashMap};
+use bevy::window::Windows;
 
 use crate::axislike::{
-    AxisType, DualAxisData, MouseMotionAxisType, MouseWheelAxisType, SingleAxis, VirtualDPad,
+    AxisType, DeadzoneCurve, DualAxis, DualAxisData, MouseMotionAxisType, MouseWheelAxisType,
+    SingleAxis, VirtualDPad,
 };
 use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
-use crate::user_input::{InputKind, UserInput};
+use crate::gamepad_profiles::{
+    DisconnectGrace, GamepadButtonRemap, GamepadCalibration, GamepadProfiles,
+};
+use crate::input_alias::InputAlias;
+use crate::input_context::{ActiveContexts, InputContext};
+use crate::input_history::{AccumulatedInput, GamepadState, InputHistory, MouseSampleRate};
+use crate::input_hooks::InputMatchHooks;
+use crate::input_macro::InputFrame;
+use crate::plugin::{ExternalFocus, TextInputActive};
+use crate::user_input::{
+    reserved_os_chords, InputCategory, InputKind, InputKindMask, Modifier, TriggerEdge, UserInput,
+};
 
 /// A collection of [`Input`] structs, which can be used to update an [`InputMap`](crate::input_map::InputMap).
 ///
 /// These are typically collected via a system from the [`World`](bevy::prelude::World) as resources.
+///
+/// Event-based inputs such as [`MouseWheel`] and [`MouseMotion`] are not cached:
+/// they are re-read from the underlying [`Events`] each time they are queried.
+/// As a result, there is nothing to invalidate when new events arrive;
+/// simply construct a new [`InputStreams`] (e.g. once per fixed timestep) to observe them.
 #[derive(Debug, Clone)]
 pub struct InputStreams<'a> {
     /// A [`GamepadButton`] [`Input`] stream
@@ -32,14 +56,256 @@ pub struct InputStreams<'a> {
     pub gamepads: &'a Gamepads,
     /// A [`KeyCode`] [`Input`] stream
     pub keycode: &'a Input<KeyCode>,
-    /// A [`MouseButton`] [`Input`] stream
-    pub mouse_button: &'a Input<MouseButton>,
+    /// A [`ScanCode`] [`Input`] stream
+    pub scan_codes: &'a Input<ScanCode>,
+    /// A [`KeyboardInput`] [`Events`] stream
+    pub keyboard_events: &'a Events<KeyboardInput>,
+    /// A [`MouseButton`] [`Input`] stream, if any
+    ///
+    /// Without this stream, mouse buttons are never pressed,
+    /// and chords that contain them are never pressed either.
+    pub mouse_button: Option<&'a Input<MouseButton>>,
     /// A [`MouseWheel`] event stream
     pub mouse_wheel: &'a Events<MouseWheel>,
     /// A [`MouseMotion`] event stream
     pub mouse_motion: &'a Events<MouseMotion>,
+    /// A [`Touches`] stream
+    pub touches: &'a Touches,
+    /// The [`InputHistory`] used to evaluate stateful inputs, if any
+    pub input_history: Option<&'a InputHistory>,
+    /// The [`InputAlias`] registry used to resolve [`UserInput::Alias`] inputs, if any
+    pub input_aliases: Option<&'a InputAlias>,
+    /// The [`InputMatchHooks`] run when inputs match, if any
+    pub input_match_hooks: Option<&'a InputMatchHooks>,
+    /// The [`MouseSampleRate`] measured by the plugin, if any
+    pub mouse_sample_rate: Option<&'a MouseSampleRate>,
+    /// The [`GamepadProfiles`] applied to raw gamepad axis values, if any
+    pub gamepad_profiles: Option<&'a GamepadProfiles>,
+    /// The [`GamepadCalibration`] subtracted from raw gamepad axis values, if any
+    pub gamepad_calibration: Option<&'a GamepadCalibration>,
+    /// The [`GamepadButtonRemap`] applied to gamepad buttons before they are matched, if any
+    pub gamepad_button_remap: Option<&'a GamepadButtonRemap>,
+    /// The [`DisconnectGrace`] for which disconnected gamepads keep their last-known state, if any
+    pub disconnect_grace: Option<&'a DisconnectGrace>,
+    /// The [`ActiveContexts`] checked by [`input_pressed_in_context`](InputStreams::input_pressed_in_context), if any
+    pub active_contexts: Option<&'a ActiveContexts>,
+    /// The [`AnalogKeyboard`] pressures read for keyboard keys, if any
+    pub analog_keyboard: Option<&'a AnalogKeyboard>,
+    /// Does a text field have focus?
+    ///
+    /// While this is `true`, keyboard inputs are never pressed. Read from the [`TextInputActive`] resource.
+    pub text_input_active: bool,
+    /// Is input focus held by something outside of the game?
+    ///
+    /// While this is `true`, inputs in `external_focus_inputs` are never pressed. Read from the [`ExternalFocus`] resource.
+    pub external_focus: bool,
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
+    /// A set of [`Gamepad`]s that will all be treated as the same player
+    ///
+    /// If this is not empty, it is used instead of the [`associated_gamepad`](Self::associated_gamepad):
+    /// gamepad buttons are pressed if they are pressed on any of these gamepads,
+    /// and gamepad axes use the value with the largest magnitude.
+    pub associated_gamepads: PetitSet<Gamepad, 8>,
+    /// The maximum magnitude of each individual [`MouseMotion`] event
+    ///
+    /// Larger events will be scaled down to this length before they are accumulated,
+    /// avoiding sudden jumps when the window regains focus or the cursor is warped.
+    /// If this is `None`, events are not clamped.
+    pub max_delta_per_event: Option<f32>,
+    /// The minimum accumulated [`MouseWheel`] movement along an axis, in pixels, for the wheel to count as moved
+    ///
+    /// Accumulated movement with a smaller magnitude is ignored,
+    /// avoiding spurious matches from tiny trackpad scroll events.
+    pub scroll_threshold: f32,
+    /// Which [`MouseWheel`] events are used when both line-based and pixel-based events arrive in the same frame
+    pub scroll_source_priority: ScrollSourcePriority,
+    /// The largest accumulated [`MouseWheel`] movement along each axis in a single frame, in pixels
+    ///
+    /// If this is `None`, the accumulated movement is not clamped.
+    pub max_scroll_per_frame: Option<Vec2>,
+    /// The number of pixels that each line of [`MouseWheel`] movement is converted into
+    ///
+    /// If this is `None`, [`DEFAULT_PIXELS_PER_LINE`] is used.
+    pub pixels_per_line: Option<f32>,
+    /// The largest magnitude that values are expected to have
+    ///
+    /// With the `debug_input_warnings` feature enabled in debug builds,
+    /// a warning is logged whenever [`input_value`](Self::input_value) or [`input_axis_pair`](Self::input_axis_pair) exceeds this.
+    pub value_warning_bound: f32,
+    /// How the opposing directions of each [`VirtualDPad`] axis are combined
+    pub digital_analog_mode: DigitalAnalogMode,
+    /// Whether the pressed state of keyboard keys is read from the polled [`Input`] resources, or the [`KeyboardInput`] events
+    pub keyboard_state_source: KeyboardStateSource,
+    /// The inputs that are suppressed while [`external_focus`](Self::external_focus) is `true`
+    ///
+    /// This is copied from [`InputMap::external_focus_inputs`](crate::input_map::InputMap::external_focus_inputs).
+    pub external_focus_inputs: PetitSet<InputKind, 16>,
+    /// Was this input replicated from a remote source, rather than generated locally?
+    ///
+    /// When updating the [`ActionState`](crate::action_state::ActionState) of an entity,
+    /// this is copied from its [`RemoteInput`] component, if any.
+    pub remote_input: RemoteInput,
+    /// Whether mouse motion axes read the relative movement of the mouse, or the absolute position of the cursor
+    pub mouse_motion_mode: MouseMotionMode,
+    /// Is the cursor grabbed by the window?
+    ///
+    /// This is read from the primary window when constructed from the [`World`], and is `true` if there is none.
+    pub cursor_grabbed: bool,
+    /// The position of the cursor in the primary window, if it is within that window
+    pub cursor_position: Option<Vec2>,
+    /// Can the guide button ([`GamepadButtonType::Mode`]) be pressed?
+    ///
+    /// The guide button is often intercepted by the operating system,
+    /// so it is ignored and never pressed unless this is set to `true`.
+    /// Defaults to `false`.
+    pub allow_guide_button: bool,
+}
+
+/// Where the values of mouse motion axes are read from
+///
+/// Set via [`InputStreams::mouse_motion_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseMotionMode {
+    /// Always read the [`total_mouse_movement`](InputStreams::total_mouse_movement)
+    Relative,
+    /// Read the [`total_mouse_movement`](InputStreams::total_mouse_movement) while the cursor is grabbed,
+    /// and the absolute [`cursor_position`](InputStreams::cursor_position) while it is free
+    FollowCursorGrab,
+}
+
+impl Default for MouseMotionMode {
+    fn default() -> Self {
+        MouseMotionMode::Relative
+    }
+}
+
+/// Which devices produced motion this frame
+///
+/// Returned by [`InputStreams::motion_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MotionSource {
+    /// Neither the mouse nor any gamepad stick moved
+    None,
+    /// Only the mouse moved
+    Mouse,
+    /// Only a gamepad stick moved
+    Gamepad,
+    /// Both the mouse and a gamepad stick moved
+    Both,
+}
+
+/// Whether a button was tapped or held, as resolved when it is released
+///
+/// Returned by [`InputStreams::tap_or_hold`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TapHold {
+    /// The button was released before the hold threshold
+    Tap,
+    /// The button was held for at least the hold threshold
+    Hold,
+}
+
+/// A resource that stores the analog travel of keyboard keys, for keyboards that report it
+///
+/// Bevy only reports whether keys are pressed, so this must be populated from your own HID integration.
+/// Keys with a recorded pressure use it as their [`InputStreams::input_value`],
+/// while all other keys fall back to their binary value.
+/// Whether a key is pressed is not affected.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AnalogKeyboard {
+    pressures: HashMap<KeyCode, f32>,
+}
+
+impl AnalogKeyboard {
+    /// Records the `pressure` of the `keycode`, typically between `0.0` and `1.0`, replacing any existing value
+    pub fn set(&mut self, keycode: KeyCode, pressure: f32) {
+        self.pressures.insert(keycode, pressure);
+    }
+
+    /// The pressure recorded for the `keycode`, if any
+    #[must_use]
+    pub fn get(&self, keycode: KeyCode) -> Option<f32> {
+        self.pressures.get(&keycode).copied()
+    }
+
+    /// Removes the pressure recorded for the `keycode`, returning it if it existed
+    pub fn remove(&mut self, keycode: KeyCode) -> Option<f32> {
+        self.pressures.remove(&keycode)
+    }
+
+    /// Removes every recorded pressure, so that all keys fall back to their binary value
+    pub fn clear(&mut self) {
+        self.pressures.clear();
+    }
+}
+
+/// Marks input as replicated from a remote source, such as another player over the network
+///
+/// Add this as a component alongside an [`InputMap`](crate::input_map::InputMap) to flag the [`InputStreams`] used to update that entity,
+/// then check [`InputStreams::is_remote`] to branch on the source of the input, such as to skip local prediction.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RemoteInput(pub bool);
+
+/// The default number of pixels that each line of [`MouseWheel`] movement is converted into
+///
+/// This is an arbitrary scale, chosen to make line and pixel events more similar.
+pub const DEFAULT_PIXELS_PER_LINE: f32 = 14.0;
+
+/// A resource that configures how the [`InputStreams`] used to update each [`ActionState`](crate::action_state::ActionState) read their inputs
+///
+/// Each field is copied into the [`InputStreams`] field of the same name: see there for details.
+/// If this resource does not exist, the defaults are used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputSettings {
+    /// See [`InputStreams::max_delta_per_event`]. Defaults to `None`.
+    pub max_delta_per_event: Option<f32>,
+    /// See [`InputStreams::scroll_threshold`]. Defaults to `0.0`.
+    pub scroll_threshold: f32,
+    /// See [`InputStreams::scroll_source_priority`]. Defaults to [`ScrollSourcePriority::Sum`].
+    pub scroll_source_priority: ScrollSourcePriority,
+    /// See [`InputStreams::max_scroll_per_frame`]. Defaults to `None`.
+    pub max_scroll_per_frame: Option<Vec2>,
+    /// See [`InputStreams::pixels_per_line`]. Defaults to `None`.
+    pub pixels_per_line: Option<f32>,
+    /// See [`InputStreams::value_warning_bound`]. Defaults to `1.0`.
+    pub value_warning_bound: f32,
+    /// See [`InputStreams::digital_analog_mode`]. Defaults to [`DigitalAnalogMode::Sum`].
+    pub digital_analog_mode: DigitalAnalogMode,
+    /// See [`InputStreams::keyboard_state_source`]. Defaults to [`KeyboardStateSource::Polled`].
+    pub keyboard_state_source: KeyboardStateSource,
+    /// See [`InputStreams::mouse_motion_mode`]. Defaults to [`MouseMotionMode::Relative`].
+    pub mouse_motion_mode: MouseMotionMode,
+    /// See [`InputStreams::allow_guide_button`]. Defaults to `false`.
+    pub allow_guide_button: bool,
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        InputSettings {
+            max_delta_per_event: None,
+            scroll_threshold: 0.0,
+            scroll_source_priority: ScrollSourcePriority::Sum,
+            max_scroll_per_frame: None,
+            pixels_per_line: None,
+            value_warning_bound: 1.0,
+            digital_analog_mode: DigitalAnalogMode::Sum,
+            keyboard_state_source: KeyboardStateSource::Polled,
+            mouse_motion_mode: MouseMotionMode::Relative,
+            allow_guide_button: false,
+        }
+    }
+}
+
+/// Is the cursor grabbed by the primary window, and where is it within that window?
+///
+/// If there is no primary window, the cursor is treated as grabbed, with no position.
+pub(crate) fn primary_cursor_state(windows: Option<&Windows>) -> (bool, Option<Vec2>) {
+    let primary_window = windows.and_then(|windows| windows.get_primary());
+    let cursor_grabbed = primary_window.map_or(true, |window| window.cursor_locked());
+    let cursor_position = primary_window.and_then(|window| window.cursor_position());
+
+    (cursor_grabbed, cursor_position)
 }
 
 // Constructors
@@ -51,9 +317,36 @@ impl<'a> InputStreams<'a> {
         let gamepad_axes = world.resource::<Axis<GamepadAxis>>();
         let gamepads = world.resource::<Gamepads>();
         let keyboard = world.resource::<Input<KeyCode>>();
-        let mouse = world.resource::<Input<MouseButton>>();
+        let scan_codes = world.resource::<Input<ScanCode>>();
+        let keyboard_events = world.resource::<Events<KeyboardInput>>();
+        let mouse = world.get_resource::<Input<MouseButton>>();
         let mouse_wheel = world.resource::<Events<MouseWheel>>();
         let mouse_motion = world.resource::<Events<MouseMotion>>();
+        let touches = world.resource::<Touches>();
+        let input_history = world.get_resource::<InputHistory>();
+        let input_aliases = world.get_resource::<InputAlias>();
+        let input_match_hooks = world.get_resource::<InputMatchHooks>();
+        let mouse_sample_rate = world.get_resource::<MouseSampleRate>();
+        let gamepad_profiles = world.get_resource::<GamepadProfiles>();
+        let gamepad_calibration = world.get_resource::<GamepadCalibration>();
+        let gamepad_button_remap = world.get_resource::<GamepadButtonRemap>();
+        let disconnect_grace = world.get_resource::<DisconnectGrace>();
+        let active_contexts = world.get_resource::<ActiveContexts>();
+        let analog_keyboard = world.get_resource::<AnalogKeyboard>();
+        let text_input_active = world
+            .get_resource::<TextInputActive>()
+            .map(|text_input_active| text_input_active.0)
+            .unwrap_or_default();
+        let external_focus = world
+            .get_resource::<ExternalFocus>()
+            .map(|external_focus| external_focus.0)
+            .unwrap_or_default();
+        let input_settings = world
+            .get_resource::<InputSettings>()
+            .copied()
+            .unwrap_or_default();
+        let (cursor_grabbed, cursor_position) =
+            primary_cursor_state(world.get_resource::<Windows>());
 
         InputStreams {
             gamepad_buttons,
@@ -61,10 +354,40 @@ impl<'a> InputStreams<'a> {
             gamepad_axes,
             gamepads,
             keycode: keyboard,
+            scan_codes,
+            keyboard_events,
             mouse_button: mouse,
             mouse_wheel,
             mouse_motion,
+            touches,
+            input_history,
+            input_aliases,
+            input_match_hooks,
+            mouse_sample_rate,
+            gamepad_profiles,
+            gamepad_calibration,
+            gamepad_button_remap,
+            disconnect_grace,
+            active_contexts,
+            analog_keyboard,
+            text_input_active,
+            external_focus,
             associated_gamepad: gamepad,
+            associated_gamepads: PetitSet::default(),
+            max_delta_per_event: input_settings.max_delta_per_event,
+            scroll_threshold: input_settings.scroll_threshold,
+            scroll_source_priority: input_settings.scroll_source_priority,
+            max_scroll_per_frame: input_settings.max_scroll_per_frame,
+            pixels_per_line: input_settings.pixels_per_line,
+            value_warning_bound: input_settings.value_warning_bound,
+            digital_analog_mode: input_settings.digital_analog_mode,
+            keyboard_state_source: input_settings.keyboard_state_source,
+            external_focus_inputs: PetitSet::default(),
+            remote_input: RemoteInput(false),
+            mouse_motion_mode: input_settings.mouse_motion_mode,
+            cursor_grabbed,
+            cursor_position,
+            allow_guide_button: input_settings.allow_guide_button,
         }
     }
 }
@@ -74,16 +397,147 @@ impl<'a> InputStreams<'a> {
     /// Guess which registered [`Gamepad`] should be used.
     ///
     /// If an associated gamepad is set, use that.
-    /// Otherwise use the first registered gamepad, if any.
+    /// Otherwise use the registered gamepad with the lowest id, if any.
     pub fn guess_gamepad(&self) -> Option<Gamepad> {
         match self.associated_gamepad {
             Some(gamepad) => Some(gamepad),
-            None => self.gamepads.iter().next().copied(),
+            None => self.ordered_gamepads().first().copied(),
+        }
+    }
+
+    /// All registered gamepads, sorted by their id
+    ///
+    /// Unlike iterating over [`Gamepads`] directly, this order is stable across frames.
+    #[must_use]
+    pub fn ordered_gamepads(&self) -> Vec<Gamepad> {
+        let mut gamepads: Vec<Gamepad> = self.gamepads.iter().copied().collect();
+        gamepads.sort_by_key(|gamepad| gamepad.id);
+        gamepads
+    }
+
+    /// Was this input replicated from a remote source, rather than generated locally?
+    ///
+    /// See [`RemoteInput`] for more details.
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        self.remote_input.0
+    }
+
+    /// The gamepads that gamepad inputs should be read from
+    ///
+    /// This is the [`associated_gamepads`](Self::associated_gamepads) if any are set,
+    /// and the result of [`guess_gamepad`](Self::guess_gamepad) otherwise.
+    pub fn gamepads_to_read(&self) -> PetitSet<Gamepad, 8> {
+        if self.associated_gamepads.is_empty() {
+            self.guess_gamepad().into_iter().collect()
+        } else {
+            self.associated_gamepads.clone()
+        }
+    }
+
+    /// The first registered gamepad with a pressed button, and that button
+    ///
+    /// Gamepads are checked in order of their id, and buttons in the order of [`GAMEPAD_BUTTON_ORDER`].
+    /// This ignores any associated gamepads, and is intended for "press any button to join" flows.
+    #[must_use]
+    pub fn first_gamepad_press(&self) -> Option<(Gamepad, GamepadButtonType)> {
+        for gamepad in self.ordered_gamepads() {
+            for button_type in GAMEPAD_BUTTON_ORDER {
+                if self.gamepad_buttons.pressed(GamepadButton {
+                    gamepad,
+                    button_type,
+                }) {
+                    return Some((gamepad, button_type));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Is the `button` pressed on any registered gamepad other than the associated ones?
+    ///
+    /// The [`associated_gamepad`](Self::associated_gamepad) and [`associated_gamepads`](Self::associated_gamepads) are ignored,
+    /// which is useful for reading input from every player except the local one, such as for spectators.
+    /// If no gamepad is associated, every registered gamepad is checked.
+    #[must_use]
+    pub fn button_pressed_other_gamepads(&self, button: GamepadButtonType) -> bool {
+        if button == GamepadButtonType::Mode && !self.allow_guide_button {
+            return false;
+        }
+
+        let button_type = self.remapped_gamepad_button(button);
+        self.gamepads.iter().any(|&gamepad| {
+            self.associated_gamepad != Some(gamepad)
+                && !self.associated_gamepads.contains(&gamepad)
+                && self.gamepad_buttons.pressed(GamepadButton {
+                    gamepad,
+                    button_type,
+                })
+        })
+    }
+
+    /// Has any gamepad ever been connected?
+    ///
+    /// This is read from the [`InputHistory`], and remains `true` after all gamepads have been disconnected.
+    /// Without an [`InputHistory`], this only checks whether a gamepad is currently connected.
+    #[must_use]
+    pub fn gamepad_ever_connected(&self) -> bool {
+        match self.input_history {
+            Some(input_history) => input_history.gamepad_ever_connected(),
+            None => self.gamepads.iter().next().is_some(),
+        }
+    }
+
+    /// The physical button read in place of the bound `button_type`, according to the [`GamepadButtonRemap`]
+    fn remapped_gamepad_button(&self, button_type: GamepadButtonType) -> GamepadButtonType {
+        match self.gamepad_button_remap {
+            Some(gamepad_button_remap) => gamepad_button_remap.get(button_type),
+            None => button_type,
         }
     }
 
     /// Is the `input` matched by the [`InputStreams`]?
+    ///
+    /// If the `input` is pressed, any hook registered for it in the [`InputMatchHooks`] is run.
     pub fn input_pressed(&self, input: &UserInput) -> bool {
+        let pressed = self.unchecked_input_pressed(input);
+
+        if pressed {
+            self.run_input_match_hook(input, || self.unchecked_input_value(input));
+        }
+
+        pressed
+    }
+
+    /// Is the `input` matched by the [`InputStreams`], while the `context` is active?
+    ///
+    /// Contexts are activated in the [`ActiveContexts`] resource.
+    /// If that resource does not exist, no contexts are active and this always returns `false`.
+    #[must_use]
+    pub fn input_pressed_in_context(&self, input: &UserInput, context: &InputContext) -> bool {
+        let context_active = self
+            .active_contexts
+            .map(|active_contexts| active_contexts.is_active(context))
+            .unwrap_or_default();
+
+        context_active && self.input_pressed(input)
+    }
+
+    /// Runs the hook registered for the `input` in the [`InputMatchHooks`], if any
+    ///
+    /// The `value` is only computed if a hook exists.
+    fn run_input_match_hook(&self, input: &UserInput, value: impl FnOnce() -> f32) {
+        if let Some(hook) = self
+            .input_match_hooks
+            .and_then(|input_match_hooks| input_match_hooks.get(input))
+        {
+            hook(input, value());
+        }
+    }
+
+    /// Is the `input` matched by the [`InputStreams`], without running any hooks?
+    pub(crate) fn unchecked_input_pressed(&self, input: &UserInput) -> bool {
         match input {
             UserInput::Single(button) => self.button_pressed(*button),
             UserInput::Chord(buttons) => self.all_buttons_pressed(buttons),
@@ -92,6 +546,7 @@ impl<'a> InputStreams<'a> {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 for button in [up, down, left, right] {
                     if self.button_pressed(*button) {
@@ -100,6 +555,274 @@ impl<'a> InputStreams<'a> {
                 }
                 false
             }
+            UserInput::Toggle { input } => match self.input_history {
+                Some(input_history) => input_history.toggled(*input, self.guess_gamepad()),
+                None => false,
+            },
+            UserInput::Charge { input, max } => match self.input_history {
+                Some(input_history) => input_history
+                    .charge(*input, self.guess_gamepad(), *max)
+                    .is_some(),
+                None => false,
+            },
+            UserInput::HoldTap { hold, tap } => {
+                self.press_kind(hold) == PressKind::Held
+                    && self.press_kind(tap) == PressKind::JustPressed
+            }
+            UserInput::Staircase { steps } => match self.input_history {
+                Some(input_history) => {
+                    !steps.is_empty()
+                        && input_history.staircase_progress(steps, self.guess_gamepad())
+                            == steps.len()
+                }
+                None => false,
+            },
+            UserInput::Shake {
+                axis,
+                reversals,
+                window,
+            } => match self.input_history {
+                Some(input_history) => {
+                    input_history.axis_reversals(*axis, self.guess_gamepad(), *window) >= *reversals
+                }
+                None => false,
+            },
+            UserInput::Debounced {
+                input: button,
+                cooldown,
+            } => match self.input_history {
+                Some(input_history) => {
+                    input_history.debounced(*button, self.guess_gamepad(), *cooldown)
+                }
+                None => false,
+            },
+            UserInput::DoubleTapHold {
+                input: button,
+                tap_window,
+            } => match self.input_history {
+                Some(input_history) => {
+                    input_history.double_tap_held(*button, self.guess_gamepad(), *tap_window)
+                }
+                None => false,
+            },
+            UserInput::OnEdge {
+                input: button,
+                edge,
+            } => match edge {
+                TriggerEdge::Press => self.press_kind(button) == PressKind::JustPressed,
+                TriggerEdge::Release => self.press_kind(button) == PressKind::Released,
+            },
+            UserInput::Alias(_) => match self.resolve_alias(input) {
+                Some(aliased_input) => self.unchecked_input_pressed(aliased_input),
+                None => false,
+            },
+            UserInput::CircleGesture(gesture) => match (self.input_history, self.guess_gamepad()) {
+                (Some(input_history), Some(gamepad)) => {
+                    let swept_angle =
+                        input_history.swept_angle(&gesture.axis, gamepad, gesture.window);
+                    swept_angle.abs() >= gesture.min_angle_swept
+                }
+                _ => false,
+            },
+            UserInput::Pinch => self.unchecked_input_value(input) != 0.0,
+            UserInput::AxisCross(axis_cross) => match self.input_history {
+                Some(input_history) => {
+                    let raw_axis: UserInput = axis_cross.raw_axis().into();
+                    // Axes rest at zero before their first recorded value
+                    let previous_value = input_history
+                        .previous_value(&self.gamepads_to_read(), &raw_axis)
+                        .unwrap_or(0.0);
+                    let value = self.unchecked_input_value(&raw_axis);
+
+                    previous_value < axis_cross.threshold && value >= axis_cross.threshold
+                }
+                None => false,
+            },
+            UserInput::StickOpposition(stick_opposition) => {
+                let left_stick = self.unchecked_input_axis_pair(&DualAxis::left_stick().into());
+                let right_stick = self.unchecked_input_axis_pair(&DualAxis::right_stick().into());
+
+                match (left_stick, right_stick) {
+                    (Some(left_stick), Some(right_stick)) => {
+                        stick_opposition.opposed(left_stick.xy(), right_stick.xy())
+                    }
+                    _ => false,
+                }
+            }
+            UserInput::ScrollWhileHeld { button } => self.scroll_while_held(*button) != Vec2::ZERO,
+            UserInput::Preferred(inputs) => match self.preferred_input(inputs) {
+                Some(preferred_input) => self.unchecked_input_pressed(preferred_input),
+                None => false,
+            },
+            UserInput::Custom(custom_input) => custom_input.value(self) != 0.0,
+        }
+    }
+
+    /// Can the `input` currently be read, given the connected devices?
+    ///
+    /// Inputs that depend on a gamepad are only satisfiable while there is a gamepad to read from,
+    /// while keyboard, mouse and touch inputs are always satisfiable.
+    /// A [`UserInput::Preferred`] is satisfiable if any of its inputs are.
+    #[must_use]
+    pub fn input_satisfiable(&self, input: &UserInput) -> bool {
+        match input {
+            UserInput::Preferred(inputs) => self.preferred_input(inputs).is_some(),
+            UserInput::Alias(_) => match self.resolve_alias(input) {
+                Some(aliased_input) => self.input_satisfiable(aliased_input),
+                None => false,
+            },
+            _ => {
+                let needs_gamepad = input
+                    .input_kinds()
+                    .iter()
+                    .any(|button| InputKindMask::of(button).contains(InputKindMask::GAMEPAD));
+
+                !needs_gamepad || !self.gamepads_to_read().is_empty()
+            }
+        }
+    }
+
+    /// The last-known states of the disconnected gamepads that are still within their [`DisconnectGrace`]
+    ///
+    /// A disconnected gamepad is only read if it is associated with these streams,
+    /// or if no gamepad is associated and no other gamepad would be read in its place.
+    fn graced_gamepad_states(&self) -> Vec<(Gamepad, &'a GamepadState)> {
+        let (input_history, disconnect_grace) = match (self.input_history, self.disconnect_grace) {
+            (Some(input_history), Some(disconnect_grace)) => (input_history, disconnect_grace),
+            _ => return Vec::new(),
+        };
+
+        input_history
+            .disconnected_gamepads(disconnect_grace.0)
+            .filter(|(gamepad, _)| {
+                if self.associated_gamepads.is_empty() {
+                    // The gamepad may still be registered on the frame that it disconnects
+                    self.guess_gamepad()
+                        .map_or(true, |guessed_gamepad| guessed_gamepad == *gamepad)
+                } else {
+                    self.associated_gamepads.contains(gamepad)
+                }
+            })
+            .collect()
+    }
+
+    /// The first of the `inputs` that is [satisfiable](Self::input_satisfiable), if any
+    fn preferred_input<'b>(&self, inputs: &'b [UserInput]) -> Option<&'b UserInput> {
+        inputs.iter().find(|input| self.input_satisfiable(input))
+    }
+
+    /// Is the `input` pressed, considering only its members within the provided `kinds`?
+    ///
+    /// Members outside of the mask are treated as not pressed, so a chord that mixes categories
+    /// cannot be pressed unless all of them are allowed.
+    /// [`UserInput::Custom`] inputs are opaque, and are only read if every category is allowed.
+    #[must_use]
+    pub fn input_pressed_filtered(&self, input: &UserInput, kinds: InputKindMask) -> bool {
+        let allowed = |button: &InputKind| kinds.contains(InputKindMask::of(button));
+
+        match input {
+            UserInput::Single(button) => allowed(button) && self.button_pressed(*button),
+            UserInput::Chord(buttons) => buttons
+                .iter()
+                .all(|button| allowed(button) && self.button_pressed(*button)),
+            UserInput::VirtualDPad(VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+                ..
+            }) => [up, down, left, right]
+                .into_iter()
+                .any(|button| allowed(button) && self.button_pressed(*button)),
+            UserInput::Toggle { input: button }
+            | UserInput::Charge { input: button, .. }
+            | UserInput::Debounced { input: button, .. }
+            | UserInput::DoubleTapHold { input: button, .. }
+            | UserInput::OnEdge { input: button, .. } => {
+                allowed(button) && self.input_pressed(input)
+            }
+            UserInput::HoldTap { hold, tap } => {
+                allowed(hold) && allowed(tap) && self.input_pressed(input)
+            }
+            UserInput::Staircase { steps } => {
+                steps.iter().all(allowed) && self.input_pressed(input)
+            }
+            UserInput::Shake { axis, .. } => {
+                kinds.contains(InputKindMask::of_axis(*axis)) && self.input_pressed(input)
+            }
+            UserInput::Alias(_) => match self.resolve_alias(input) {
+                Some(aliased_input) => self.input_pressed_filtered(aliased_input, kinds),
+                None => false,
+            },
+            UserInput::CircleGesture(gesture) => {
+                allowed(&InputKind::DualAxis(gesture.axis)) && self.input_pressed(input)
+            }
+            UserInput::Pinch => kinds.contains(InputKindMask::TOUCH) && self.input_pressed(input),
+            UserInput::AxisCross(axis_cross) => {
+                allowed(&InputKind::SingleAxis(axis_cross.raw_axis())) && self.input_pressed(input)
+            }
+            UserInput::StickOpposition(_) => {
+                kinds.contains(InputKindMask::GAMEPAD) && self.input_pressed(input)
+            }
+            UserInput::ScrollWhileHeld { .. } => {
+                kinds.contains(InputKindMask::MOUSE) && self.input_pressed(input)
+            }
+            UserInput::Preferred(inputs) => match self.preferred_input(inputs) {
+                Some(preferred_input) => self.input_pressed_filtered(preferred_input, kinds),
+                None => false,
+            },
+            UserInput::Custom(_) => kinds == InputKindMask::ALL && self.input_pressed(input),
+        }
+    }
+
+    /// Finds the pressed [`InputKind`]s that more than one of the `inputs` depend on
+    ///
+    /// Each shared [`InputKind`] is returned with every input that depends on it, in the order that they were provided.
+    /// This is useful for diagnosing why several actions are triggered by a single key.
+    /// See [`UserInput::input_kinds`] for which inputs are considered.
+    #[must_use]
+    pub fn shared_inputs<'b>(
+        &self,
+        inputs: impl Iterator<Item = &'b UserInput>,
+    ) -> Vec<(InputKind, Vec<&'b UserInput>)> {
+        let mut dependents_by_kind: Vec<(InputKind, Vec<&'b UserInput>)> = Vec::new();
+
+        for input in inputs {
+            for input_kind in input.input_kinds() {
+                if !self.button_pressed(input_kind) {
+                    continue;
+                }
+
+                match dependents_by_kind
+                    .iter_mut()
+                    .find(|(existing_kind, _)| *existing_kind == input_kind)
+                {
+                    Some((_, dependents)) => {
+                        // The same input may use a button more than once, such as in a virtual dpad
+                        if !dependents
+                            .iter()
+                            .any(|dependent| std::ptr::eq(*dependent, input))
+                        {
+                            dependents.push(input);
+                        }
+                    }
+                    None => dependents_by_kind.push((input_kind, vec![input])),
+                }
+            }
+        }
+
+        dependents_by_kind.retain(|(_, dependents)| dependents.len() > 1);
+        dependents_by_kind
+    }
+
+    /// Looks up the [`UserInput`] that an [`UserInput::Alias`] refers to, in the [`InputAlias`] registry
+    ///
+    /// Returns [`None`] if `input` is not an alias, or the alias is not registered.
+    #[must_use]
+    pub fn resolve_alias(&self, input: &UserInput) -> Option<&'a UserInput> {
+        match input {
+            UserInput::Alias(id) => self.input_aliases?.get(*id),
+            _ => None,
         }
     }
 
@@ -115,55 +838,92 @@ impl<'a> InputStreams<'a> {
         false
     }
 
+    /// Which of the `inputs` has the lowest non-zero [`input_value`](Self::input_value)?
+    ///
+    /// Values are compared by their magnitude, and inputs with a value of exactly zero are skipped.
+    /// Returns [`None`] if none of the `inputs` are active.
+    #[must_use]
+    pub fn weakest_active_input<'b>(
+        &self,
+        inputs: &'b PetitSet<UserInput, 16>,
+    ) -> Option<&'b UserInput> {
+        let mut weakest: Option<(&UserInput, f32)> = None;
+
+        for input in inputs.iter() {
+            let magnitude = self.input_value(input).abs();
+            if magnitude == 0.0 {
+                continue;
+            }
+
+            match weakest {
+                Some((_, weakest_magnitude)) if weakest_magnitude <= magnitude => (),
+                _ => weakest = Some((input, magnitude)),
+            }
+        }
+
+        weakest.map(|(input, _)| input)
+    }
+
     /// Is the `button` pressed?
     #[must_use]
     pub fn button_pressed(&self, button: InputKind) -> bool {
         match button {
+            // Inputs that respect external focus are suppressed while it is held elsewhere
+            _ if self.external_focus && self.external_focus_inputs.contains(&button) => false,
             InputKind::DualAxis(_) => {
-                let axis_pair = self.input_axis_pair(&UserInput::Single(button)).unwrap();
+                let axis_pair = self
+                    .unchecked_input_axis_pair(&UserInput::Single(button))
+                    .unwrap();
 
                 axis_pair.length() != 0.0
             }
             InputKind::SingleAxis(_) => {
-                let value = self.input_value(&UserInput::Single(button));
+                let value = self.unchecked_input_value(&UserInput::Single(button));
 
                 value != 0.0
             }
+            // The guide button is ignored while it is reserved for the operating system
+            InputKind::GamepadButton(GamepadButtonType::Mode) if !self.allow_guide_button => false,
             InputKind::GamepadButton(gamepad_button) => {
-                if let Some(gamepad) = self.guess_gamepad() {
+                let button_type = self.remapped_gamepad_button(gamepad_button);
+
+                self.gamepads_to_read().iter().any(|&gamepad| {
                     self.gamepad_buttons.pressed(GamepadButton {
                         gamepad,
-                        button_type: gamepad_button,
+                        button_type,
                     })
-                } else {
-                    false
-                }
+                }) || self
+                    .graced_gamepad_states()
+                    .iter()
+                    .any(|(_, gamepad_state)| gamepad_state.pressed_buttons.contains(&button_type))
             }
-            InputKind::Keyboard(keycode) => self.keycode.pressed(keycode),
-            InputKind::Mouse(mouse_button) => self.mouse_button.pressed(mouse_button),
+            // Keyboard inputs are reserved for the focused text field
+            InputKind::Keyboard(_) | InputKind::KeyLocation(_) if self.text_input_active => false,
+            InputKind::Keyboard(keycode) => match self.keyboard_state_source {
+                KeyboardStateSource::Polled => self.keycode.pressed(keycode),
+                KeyboardStateSource::Events => self
+                    .latest_keyboard_state(|event| event.key_code == Some(keycode))
+                    .unwrap_or_else(|| self.keycode.pressed(keycode)),
+            },
+            InputKind::KeyLocation(scan_code) => match self.keyboard_state_source {
+                KeyboardStateSource::Polled => self.scan_codes.pressed(scan_code),
+                KeyboardStateSource::Events => self
+                    .latest_keyboard_state(|event| event.scan_code == scan_code.0)
+                    .unwrap_or_else(|| self.scan_codes.pressed(scan_code)),
+            },
+            InputKind::Mouse(mouse_button) => self
+                .mouse_button
+                .map_or(false, |mouse_buttons| mouse_buttons.pressed(mouse_button)),
+            InputKind::TouchFinger { index } => self.touch_by_index(index).is_some(),
             InputKind::MouseWheel(mouse_wheel_direction) => {
-                let mut total_mouse_wheel_movement = 0.0;
-
-                // FIXME: verify that this works and doesn't double count events
-                let mut event_reader = self.mouse_wheel.get_reader();
-
-                // Arbitary scale to make line & pixel events more similar
-                const PIXELS_PER_LINE: f32 = 14.0;
-
-                // PERF: this summing is computed for every individual input
-                // This should probably be computed once, and then cached / read
-                // Fix upstream!
-                for mouse_wheel_event in event_reader.iter(self.mouse_wheel) {
-                    total_mouse_wheel_movement += match mouse_wheel_direction {
-                        MouseWheelDirection::Up | MouseWheelDirection::Down => mouse_wheel_event.y,
-                        MouseWheelDirection::Left | MouseWheelDirection::Right => {
-                            mouse_wheel_event.x
-                        }
-                    } * match mouse_wheel_event.unit {
-                        MouseScrollUnit::Line => PIXELS_PER_LINE,
-                        MouseScrollUnit::Pixel => 1.0,
-                    };
-                }
+                let total_mouse_wheel_movement = match mouse_wheel_direction {
+                    MouseWheelDirection::Up | MouseWheelDirection::Down => {
+                        self.total_mouse_wheel_movement().y
+                    }
+                    MouseWheelDirection::Left | MouseWheelDirection::Right => {
+                        self.total_mouse_wheel_movement().x
+                    }
+                };
 
                 match mouse_wheel_direction {
                     MouseWheelDirection::Up | MouseWheelDirection::Right => {
@@ -174,36 +934,618 @@ impl<'a> InputStreams<'a> {
                     }
                 }
             }
-            // CLEANUP: refactor to share code with MouseWheel
-            InputKind::MouseMotion(mouse_motion_direction) => {
-                let mut total_mouse_movement = 0.0;
-
-                // FIXME: verify that this works and doesn't double count events
-                let mut event_reader = self.mouse_motion.get_reader();
+            InputKind::MouseMotion(mouse_motion_direction) => {
+                let total_mouse_movement = match mouse_motion_direction {
+                    MouseMotionDirection::Up | MouseMotionDirection::Down => {
+                        self.total_mouse_movement().y
+                    }
+                    MouseMotionDirection::Left | MouseMotionDirection::Right => {
+                        self.total_mouse_movement().x
+                    }
+                };
+
+                match mouse_motion_direction {
+                    MouseMotionDirection::Up | MouseMotionDirection::Right => {
+                        total_mouse_movement > 0.0
+                    }
+                    MouseMotionDirection::Down | MouseMotionDirection::Left => {
+                        total_mouse_movement < 0.0
+                    }
+                }
+            }
+        }
+    }
+
+    /// The `index`-th active [`Touch`], with touches ordered by their id
+    ///
+    /// Returns [`None`] if there are not enough active touches.
+    #[must_use]
+    pub fn touch_by_index(&self, index: usize) -> Option<&'a Touch> {
+        let mut touches: Vec<&Touch> = self.touches.iter().collect();
+        touches.sort_by_key(|touch| touch.id());
+
+        touches.get(index).copied()
+    }
+
+    /// Clones all of the [`MouseWheel`] events in the stream, from oldest to newest
+    ///
+    /// Unlike [`total_mouse_wheel_movement`](Self::total_mouse_wheel_movement),
+    /// no unit conversion or thresholds are applied.
+    /// The events are read with a fresh reader, so they are not consumed.
+    /// Events do not carry sub-frame timestamps, but their arrival order is preserved:
+    /// use [`mouse_wheel_checkpoint`](Self::mouse_wheel_checkpoint) and
+    /// [`split_mouse_wheel_events`](Self::split_mouse_wheel_events) to find which events arrived before a point in the frame.
+    #[must_use]
+    pub fn mouse_wheel_events(&self) -> Vec<MouseWheel> {
+        let mut mouse_wheel_reader = self.mouse_wheel.get_reader();
+
+        mouse_wheel_reader.iter(self.mouse_wheel).cloned().collect()
+    }
+
+    /// The total [`MouseWheel`] movement accumulated from all events in the stream, in pixels
+    ///
+    /// Line-based scrolling is converted to pixels using the [`pixels_per_line`](Self::pixels_per_line).
+    /// Along each axis, the movement is clamped to the [`max_scroll_per_frame`](Self::max_scroll_per_frame),
+    /// and movement smaller than the [`scroll_threshold`](Self::scroll_threshold) is ignored.
+    #[must_use]
+    pub fn total_mouse_wheel_movement(&self) -> Vec2 {
+        let mut total_mouse_wheel_movement = Vec2::ZERO;
+
+        // FIXME: verify that this works and doesn't double count events
+        let mut event_reader = self.mouse_wheel.get_reader();
+
+        let pixels_per_line = self.pixels_per_line.unwrap_or(DEFAULT_PIXELS_PER_LINE);
+
+        // If both kinds of events are present, the preferred source may ignore the other
+        let mut unit_reader = self.mouse_wheel.get_reader();
+        let (mut has_lines, mut has_pixels) = (false, false);
+        for mouse_wheel_event in unit_reader.iter(self.mouse_wheel) {
+            match mouse_wheel_event.unit {
+                MouseScrollUnit::Line => has_lines = true,
+                MouseScrollUnit::Pixel => has_pixels = true,
+            }
+        }
+
+        let ignored_unit = match self.scroll_source_priority {
+            ScrollSourcePriority::PreferPixel if has_lines && has_pixels => {
+                Some(MouseScrollUnit::Line)
+            }
+            ScrollSourcePriority::PreferLine if has_lines && has_pixels => {
+                Some(MouseScrollUnit::Pixel)
+            }
+            _ => None,
+        };
+
+        // PERF: this summing is computed for every individual input
+        // This should probably be computed once, and then cached / read
+        // Fix upstream!
+        for mouse_wheel_event in event_reader.iter(self.mouse_wheel) {
+            if Some(mouse_wheel_event.unit) == ignored_unit {
+                continue;
+            }
+
+            total_mouse_wheel_movement += Vec2::new(mouse_wheel_event.x, mouse_wheel_event.y)
+                * match mouse_wheel_event.unit {
+                    MouseScrollUnit::Line => pixels_per_line,
+                    MouseScrollUnit::Pixel => 1.0,
+                };
+        }
+
+        if let Some(max_scroll_per_frame) = self.max_scroll_per_frame {
+            let max_scroll_per_frame = max_scroll_per_frame.abs();
+            total_mouse_wheel_movement =
+                total_mouse_wheel_movement.clamp(-max_scroll_per_frame, max_scroll_per_frame);
+        }
+
+        if total_mouse_wheel_movement.x.abs() < self.scroll_threshold {
+            total_mouse_wheel_movement.x = 0.0;
+        }
+        if total_mouse_wheel_movement.y.abs() < self.scroll_threshold {
+            total_mouse_wheel_movement.y = 0.0;
+        }
+
+        total_mouse_wheel_movement
+    }
+
+    /// The [`total_mouse_wheel_movement`](Self::total_mouse_wheel_movement) while the `button` is held, and zero otherwise
+    fn scroll_while_held(&self, button: MouseButton) -> Vec2 {
+        if self.button_pressed(InputKind::Mouse(button)) {
+            self.total_mouse_wheel_movement()
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// The signed component of the [`total_mouse_wheel_movement`](Self::total_mouse_wheel_movement) along the provided `axis`
+    ///
+    /// Scrolling up or to the right is positive.
+    #[must_use]
+    pub fn mouse_wheel_axis(&self, axis: MouseWheelAxisType) -> f32 {
+        match axis {
+            MouseWheelAxisType::X => self.total_mouse_wheel_movement().x,
+            MouseWheelAxisType::Y => self.total_mouse_wheel_movement().y,
+        }
+    }
+
+    /// The number of whole notches scrolled by line-based [`MouseWheel`] events in the stream
+    ///
+    /// Fractional lines, as reported by some precision mice, are truncated towards zero after summing.
+    /// Unlike [`total_mouse_wheel_movement`](Self::total_mouse_wheel_movement),
+    /// no thresholds, limits or source priorities are applied.
+    #[must_use]
+    pub fn mouse_wheel_notches(&self) -> IVec2 {
+        // Casting to integers truncates towards zero
+        self.mouse_wheel_movement_in(MouseScrollUnit::Line)
+            .as_ivec2()
+    }
+
+    /// The total movement of pixel-based [`MouseWheel`] events in the stream, such as from a trackpad
+    ///
+    /// Line-based events are ignored: see [`mouse_wheel_notches`](Self::mouse_wheel_notches).
+    /// Unlike [`total_mouse_wheel_movement`](Self::total_mouse_wheel_movement),
+    /// no thresholds, limits or source priorities are applied.
+    #[must_use]
+    pub fn mouse_wheel_smooth(&self) -> Vec2 {
+        self.mouse_wheel_movement_in(MouseScrollUnit::Pixel)
+    }
+
+    /// Records the current position in the [`mouse_wheel_events`](Self::mouse_wheel_events),
+    /// for use with [`split_mouse_wheel_events`](Self::split_mouse_wheel_events)
+    ///
+    /// Checkpoints are only meaningful within the frame that they were recorded in,
+    /// as the event buffers are swapped each frame.
+    #[must_use]
+    pub fn mouse_wheel_checkpoint(&self) -> usize {
+        self.mouse_wheel_events().len()
+    }
+
+    /// Splits the [`mouse_wheel_events`](Self::mouse_wheel_events) into those that arrived before the `checkpoint`,
+    /// and those that arrived after it
+    ///
+    /// The `checkpoint` should be recorded earlier in the same frame using [`mouse_wheel_checkpoint`](Self::mouse_wheel_checkpoint).
+    #[must_use]
+    pub fn split_mouse_wheel_events(
+        &self,
+        checkpoint: usize,
+    ) -> (Vec<MouseWheel>, Vec<MouseWheel>) {
+        let mut mouse_wheel_events = self.mouse_wheel_events();
+        let after_checkpoint =
+            mouse_wheel_events.split_off(checkpoint.min(mouse_wheel_events.len()));
+
+        (mouse_wheel_events, after_checkpoint)
+    }
+
+    /// The sum of the [`MouseWheel`] events in the stream that use the provided `unit`, in that unit
+    fn mouse_wheel_movement_in(&self, unit: MouseScrollUnit) -> Vec2 {
+        let mut event_reader = self.mouse_wheel.get_reader();
+
+        event_reader
+            .iter(self.mouse_wheel)
+            .filter(|mouse_wheel_event| mouse_wheel_event.unit == unit)
+            .map(|mouse_wheel_event| Vec2::new(mouse_wheel_event.x, mouse_wheel_event.y))
+            .sum()
+    }
+
+    /// The total [`MouseMotion`] accumulated from all events in the stream
+    ///
+    /// If [`max_delta_per_event`](Self::max_delta_per_event) is set,
+    /// each event is clamped to that length before it is accumulated.
+    #[must_use]
+    pub fn total_mouse_movement(&self) -> Vec2 {
+        let mut total_mouse_movement = Vec2::ZERO;
+
+        // FIXME: verify that this works and doesn't double count events
+        let mut event_reader = self.mouse_motion.get_reader();
+
+        for mouse_motion_event in event_reader.iter(self.mouse_motion) {
+            total_mouse_movement += match self.max_delta_per_event {
+                Some(max_delta) => mouse_motion_event.delta.clamp_length_max(max_delta),
+                None => mouse_motion_event.delta,
+            };
+        }
+
+        total_mouse_movement
+    }
+
+    /// The delta of the most recent individual [`MouseMotion`] event, if any
+    ///
+    /// Unlike [`total_mouse_movement`](Self::total_mouse_movement), events are not accumulated,
+    /// and the delta is not clamped by [`max_delta_per_event`](Self::max_delta_per_event).
+    /// This is useful for raw-input aiming, where per-event precision matters.
+    #[must_use]
+    pub fn latest_mouse_motion(&self) -> Option<Vec2> {
+        let mut event_reader = self.mouse_motion.get_reader();
+
+        event_reader
+            .iter(self.mouse_motion)
+            .last()
+            .map(|mouse_motion_event| mouse_motion_event.delta)
+    }
+
+    /// The [`ScanCode`]s of every physical key that is currently pressed, sorted by their value
+    ///
+    /// The [`Input<ScanCode>`] resource is always read by [`InputStreams`],
+    /// so this is empty when no keys are pressed rather than when the resource is missing.
+    #[must_use]
+    pub fn pressed_scan_codes(&self) -> Vec<ScanCode> {
+        let mut pressed_scan_codes: Vec<ScanCode> =
+            self.scan_codes.get_pressed().copied().collect();
+        pressed_scan_codes.sort_by_key(|scan_code| scan_code.0);
+        pressed_scan_codes
+    }
+
+    /// Was any button in the `category` just pressed or just released this frame?
+    ///
+    /// Only the buttons of the [`gamepads_to_read`](Self::gamepads_to_read) are checked for [`InputCategory::Gamepad`].
+    /// This is useful for only redrawing input-related UI when something has changed.
+    #[must_use]
+    pub fn category_changed(&self, category: InputCategory) -> bool {
+        match category {
+            InputCategory::Keyboard => {
+                self.keycode.get_just_pressed().next().is_some()
+                    || self.keycode.get_just_released().next().is_some()
+                    || self.scan_codes.get_just_pressed().next().is_some()
+                    || self.scan_codes.get_just_released().next().is_some()
+            }
+            InputCategory::Mouse => self.mouse_button.map_or(false, button_changed),
+            InputCategory::Gamepad => {
+                let gamepads = self.gamepads_to_read();
+
+                self.gamepad_buttons
+                    .get_just_pressed()
+                    .chain(self.gamepad_buttons.get_just_released())
+                    .any(|gamepad_button| gamepads.contains(&gamepad_button.gamepad))
+            }
+        }
+    }
+
+    /// Was the most recent matching [`KeyboardInput`] event in the stream a press?
+    ///
+    /// Returns [`None`] if there are no matching events.
+    fn latest_keyboard_state(&self, matches: impl Fn(&KeyboardInput) -> bool) -> Option<bool> {
+        let mut keyboard_event_reader = self.keyboard_events.get_reader();
+
+        keyboard_event_reader
+            .iter(self.keyboard_events)
+            .filter(|&event| matches(event))
+            .last()
+            .map(|event| event.state == ButtonState::Pressed)
+    }
+
+    /// The mouse movement accumulated in the [`InputHistory`] since it was last drained
+    ///
+    /// Returns [`None`] if there is no [`InputHistory`].
+    /// To consume the movement once per fixed timestep, call [`InputHistory::drain_accumulated`] on the resource instead.
+    #[must_use]
+    pub fn accumulated_input(&self) -> Option<AccumulatedInput> {
+        self.input_history.map(InputHistory::accumulated)
+    }
+
+    /// The average number of [`MouseMotion`] events received per second, as measured by the [`MouseSampleRate`] resource
+    ///
+    /// Returns [`None`] if the [`MouseSampleRate`] resource does not exist.
+    #[must_use]
+    pub fn mouse_sample_rate(&self) -> Option<f32> {
+        self.mouse_sample_rate
+            .map(|mouse_sample_rate| mouse_sample_rate.events_per_second())
+    }
+
+    /// Is the length of the [`total_mouse_movement`](Self::total_mouse_movement) greater than `threshold`?
+    ///
+    /// This is useful for detecting when the mouse is idle, such as to hide a cursor.
+    #[must_use]
+    pub fn mouse_is_moving(&self, threshold: f32) -> bool {
+        self.total_mouse_movement().length() > threshold
+    }
+
+    /// The value read by mouse motion axes, according to the [`mouse_motion_mode`](Self::mouse_motion_mode)
+    ///
+    /// This is either the [`total_mouse_movement`](Self::total_mouse_movement),
+    /// or the absolute [`cursor_position`](Self::cursor_position) while the cursor is free.
+    /// If the cursor position is unknown, it is treated as [`Vec2::ZERO`].
+    #[must_use]
+    pub fn mouse_motion_value(&self) -> Vec2 {
+        match self.mouse_motion_mode {
+            MouseMotionMode::FollowCursorGrab if !self.cursor_grabbed => {
+                self.cursor_position.unwrap_or_default()
+            }
+            _ => self.total_mouse_movement(),
+        }
+    }
+
+    /// Which devices produced motion this frame
+    ///
+    /// The mouse has moved if the [`total_mouse_movement`](Self::total_mouse_movement) is not zero.
+    /// A gamepad has moved if either of its sticks is away from the center,
+    /// after any [`GamepadCalibration`] and [`GamepadProfiles`] are applied.
+    /// This is useful for finding which device is driving an action bound to both, such as looking around.
+    #[must_use]
+    pub fn motion_source(&self) -> MotionSource {
+        let mouse_moved = self.total_mouse_movement() != Vec2::ZERO;
+        let gamepad_moved = [
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+            GamepadAxisType::RightStickX,
+            GamepadAxisType::RightStickY,
+        ]
+        .into_iter()
+        .any(|axis_type| self.input_value(&SingleAxis::symmetric(axis_type, 0.0).into()) != 0.0);
+
+        match (mouse_moved, gamepad_moved) {
+            (false, false) => MotionSource::None,
+            (true, false) => MotionSource::Mouse,
+            (false, true) => MotionSource::Gamepad,
+            (true, true) => MotionSource::Both,
+        }
+    }
+
+    /// The component of the [`total_mouse_movement`](Self::total_mouse_movement) along the provided `axis`
+    ///
+    /// Motion along the other axis is discarded, constraining the mouse to a single cardinal axis.
+    #[must_use]
+    pub fn mouse_motion_constrained(&self, axis: MouseMotionAxisType) -> f32 {
+        let total_mouse_movement = self.total_mouse_movement();
+
+        match axis {
+            MouseMotionAxisType::X => total_mouse_movement.x,
+            MouseMotionAxisType::Y => total_mouse_movement.y,
+        }
+    }
+
+    /// The [`total_mouse_movement`](Self::total_mouse_movement), rotated counterclockwise by `angle` radians
+    ///
+    /// This is useful for relative aiming, where motion is measured relative to a reference frame.
+    #[must_use]
+    pub fn mouse_motion_rotated(&self, angle: f32) -> Vec2 {
+        let total_mouse_movement = self.total_mouse_movement();
+        let (sin, cos) = angle.sin_cos();
+
+        Vec2::new(
+            total_mouse_movement.x * cos - total_mouse_movement.y * sin,
+            total_mouse_movement.x * sin + total_mouse_movement.y * cos,
+        )
+    }
+
+    /// Counts the mouse motion and mouse wheel events currently stored in the stream
+    ///
+    /// This uses a fresh event reader, and does not consume any events.
+    #[must_use]
+    pub fn event_counts(&self) -> InputEventCounts {
+        let mut mouse_motion_reader = self.mouse_motion.get_reader();
+        let mut mouse_wheel_reader = self.mouse_wheel.get_reader();
+
+        InputEventCounts {
+            mouse_motion: mouse_motion_reader.iter(self.mouse_motion).count(),
+            mouse_wheel: mouse_wheel_reader.iter(self.mouse_wheel).count(),
+        }
+    }
+
+    /// Did anything happen in the stream this frame?
+    ///
+    /// This is true if there are any pending mouse motion or mouse wheel events,
+    /// or if any button was just pressed or just released.
+    /// Useful for skipping work on idle frames.
+    #[must_use]
+    pub fn has_events(&self) -> bool {
+        let mut mouse_wheel_reader = self.mouse_wheel.get_reader();
+        let mut mouse_motion_reader = self.mouse_motion.get_reader();
+
+        mouse_wheel_reader.iter(self.mouse_wheel).next().is_some()
+            || mouse_motion_reader.iter(self.mouse_motion).next().is_some()
+            || button_changed(self.keycode)
+            || button_changed(self.scan_codes)
+            || self.mouse_button.map_or(false, button_changed)
+            || button_changed(self.gamepad_buttons)
+    }
+
+    /// A rough measure of how much input happened this frame
+    ///
+    /// This is the number of buttons that were just pressed or just released,
+    /// plus the length of the mouse motion and mouse wheel movement,
+    /// plus the total change in the gamepad axes since the previous frame, as recorded in the [`InputHistory`].
+    /// [`ScanCode`]s are not counted, as they mirror the [`KeyCode`]s.
+    ///
+    /// Idle frames score `0.0`. Sampling this over time can help to detect scripted input.
+    #[must_use]
+    pub fn input_activity_score(&self) -> f32 {
+        let button_changes = button_change_count(self.keycode)
+            + self.mouse_button.map_or(0, button_change_count)
+            + button_change_count(self.gamepad_buttons);
+
+        let gamepad_axis_delta = self
+            .input_history
+            .map(|input_history| input_history.gamepad_axis_delta())
+            .unwrap_or_default();
+
+        button_changes as f32
+            + self.total_mouse_movement().length()
+            + self.total_mouse_wheel_movement().length()
+            + gamepad_axis_delta
+    }
+
+    /// Does the `input` include a chord that is intercepted by the operating system?
+    ///
+    /// The `input` includes a chord if it contains the chord's key, and either side of each of its modifiers,
+    /// so `Ctrl + Alt + F4` is reserved on Windows because of `Alt + F4`.
+    /// See [`reserved_os_chords`] for the chords checked on each platform.
+    #[must_use]
+    pub fn is_os_reserved(&self, input: &UserInput) -> bool {
+        let input_kinds = input.input_kinds();
+        let contains_key = |keycode: KeyCode| input_kinds.contains(&InputKind::Keyboard(keycode));
+
+        reserved_os_chords().iter().any(|(modifiers, keycode)| {
+            contains_key(*keycode)
+                && modifiers
+                    .iter()
+                    .all(|modifier| modifier.keycodes().into_iter().any(contains_key))
+        })
+    }
+
+    /// The set of [`Modifier`]s for which either the left or right key is currently pressed
+    #[must_use]
+    pub fn active_modifiers(&self) -> PetitSet<Modifier, 8> {
+        let mut active_modifiers = PetitSet::default();
+
+        for modifier in Modifier::ALL {
+            if modifier
+                .keycodes()
+                .iter()
+                .any(|&keycode| self.keycode.pressed(keycode))
+            {
+                active_modifiers.insert(modifier);
+            }
+        }
+
+        active_modifiers
+    }
+
+    /// How far along is the `input` towards being held for `duration`?
+    ///
+    /// Returns the time that the input has been held for divided by `duration`, clamped to `1.0`.
+    /// Returns `0.0` if the input is not held, or if there is no [`InputHistory`].
+    #[must_use]
+    pub fn hold_progress(&self, input: &InputKind, duration: Duration) -> f32 {
+        let held_duration = match self.input_history {
+            Some(input_history) => input_history.held_duration(*input, self.guess_gamepad()),
+            None => None,
+        };
+
+        match held_duration {
+            Some(_) if duration.is_zero() => 1.0,
+            Some(held_duration) => (held_duration.as_secs_f32() / duration.as_secs_f32()).min(1.0),
+            None => 0.0,
+        }
+    }
+
+    /// Was the `input` tapped or held, according to how long it was held before being released this frame?
+    ///
+    /// Presses shorter than `hold_threshold` are [`TapHold::Tap`], while longer presses are [`TapHold::Hold`].
+    /// Returns [`None`] while the input is still held, if it was not released this frame,
+    /// or if there is no [`InputHistory`].
+    #[must_use]
+    pub fn tap_or_hold(&self, input: &InputKind, hold_threshold: Duration) -> Option<TapHold> {
+        let held = self
+            .input_history?
+            .record(*input, self.guess_gamepad())?
+            .released_hold?;
+
+        if held < hold_threshold {
+            Some(TapHold::Tap)
+        } else {
+            Some(TapHold::Hold)
+        }
+    }
+
+    /// Are both `a` and `b` currently held, with their presses starting within `window` of each other?
+    ///
+    /// Unlike a near chord, the order of the presses does not matter and neither input needs to be bound to an action.
+    /// Returns `false` if there is no [`InputHistory`].
+    #[must_use]
+    pub fn pressed_together(&self, a: &InputKind, b: &InputKind, window: Duration) -> bool {
+        let input_history = match self.input_history {
+            Some(input_history) => input_history,
+            None => return false,
+        };
+
+        let gamepad = self.guess_gamepad();
+        let pressed_at = |button: &InputKind| input_history.record(*button, gamepad)?.pressed_at;
+
+        match (pressed_at(a), pressed_at(b)) {
+            (Some(a_pressed_at), Some(b_pressed_at)) => {
+                let gap = a_pressed_at
+                    .saturating_duration_since(b_pressed_at)
+                    .max(b_pressed_at.saturating_duration_since(a_pressed_at));
+                gap <= window
+            }
+            _ => false,
+        }
+    }
+
+    /// Was the `button` just pressed, held since an earlier frame, just released, or neither?
+    ///
+    /// Gamepad buttons are checked on every gamepad that is read, preferring the most recent press.
+    /// Inputs without edge detection (such as axes) are reported as [`PressKind::Held`] while pressed.
+    /// The same focus, remapping and keyboard settings as [`button_pressed`](Self::button_pressed) are respected.
+    #[must_use]
+    pub fn press_kind(&self, button: &InputKind) -> PressKind {
+        match *button {
+            _ if self.external_focus && self.external_focus_inputs.contains(button) => {
+                PressKind::None
+            }
+            InputKind::GamepadButton(GamepadButtonType::Mode) if !self.allow_guide_button => {
+                PressKind::None
+            }
+            InputKind::Keyboard(_) | InputKind::KeyLocation(_) if self.text_input_active => {
+                PressKind::None
+            }
+            InputKind::Keyboard(keycode) => {
+                self.keyboard_press_kind(*button, button_press_kind(self.keycode, keycode))
+            }
+            InputKind::KeyLocation(scan_code) => {
+                self.keyboard_press_kind(*button, button_press_kind(self.scan_codes, scan_code))
+            }
+            InputKind::Mouse(mouse_button) => match self.mouse_button {
+                Some(mouse_buttons) => button_press_kind(mouse_buttons, mouse_button),
+                None => PressKind::None,
+            },
+            InputKind::GamepadButton(gamepad_button) => {
+                let button_type = self.remapped_gamepad_button(gamepad_button);
+                let mut press_kinds: Vec<PressKind> = self
+                    .gamepads_to_read()
+                    .iter()
+                    .map(|&gamepad| {
+                        button_press_kind(
+                            self.gamepad_buttons,
+                            GamepadButton {
+                                gamepad,
+                                button_type,
+                            },
+                        )
+                    })
+                    .collect();
 
-                for mouse_motion_event in event_reader.iter(self.mouse_motion) {
-                    total_mouse_movement += match mouse_motion_direction {
-                        MouseMotionDirection::Up | MouseMotionDirection::Down => {
-                            mouse_motion_event.delta.y
-                        }
-                        MouseMotionDirection::Left | MouseMotionDirection::Right => {
-                            mouse_motion_event.delta.x
-                        }
-                    }
+                // Buttons held on a recently disconnected gamepad stay held during the grace period
+                if self
+                    .graced_gamepad_states()
+                    .iter()
+                    .any(|(_, gamepad_state)| gamepad_state.pressed_buttons.contains(&button_type))
+                {
+                    press_kinds.push(PressKind::Held);
                 }
 
-                match mouse_motion_direction {
-                    MouseMotionDirection::Up | MouseMotionDirection::Right => {
-                        total_mouse_movement > 0.0
-                    }
-                    MouseMotionDirection::Down | MouseMotionDirection::Left => {
-                        total_mouse_movement < 0.0
-                    }
+                [PressKind::JustPressed, PressKind::Held, PressKind::Released]
+                    .into_iter()
+                    .find(|press_kind| press_kinds.contains(press_kind))
+                    .unwrap_or(PressKind::None)
+            }
+            _ => {
+                if self.button_pressed(*button) {
+                    PressKind::Held
+                } else {
+                    PressKind::None
                 }
             }
         }
     }
 
+    /// Reconciles the `polled` [`PressKind`] of a keyboard `button` with the [`KeyboardStateSource`]
+    ///
+    /// When reading raw keyboard events, the latest event decides whether the key is down,
+    /// and the polled state only decides whether it was already down on an earlier frame.
+    fn keyboard_press_kind(&self, button: InputKind, polled: PressKind) -> PressKind {
+        match self.keyboard_state_source {
+            KeyboardStateSource::Polled => polled,
+            KeyboardStateSource::Events => match (self.button_pressed(button), polled) {
+                (true, PressKind::Held) => PressKind::Held,
+                (true, _) => PressKind::JustPressed,
+                (false, PressKind::Held | PressKind::JustPressed | PressKind::Released) => {
+                    PressKind::Released
+                }
+                (false, PressKind::None) => PressKind::None,
+            },
+        }
+    }
+
     /// Are all of the `buttons` pressed?
     #[must_use]
     pub fn all_buttons_pressed(&self, buttons: &PetitSet<InputKind, 8>) -> bool {
@@ -217,6 +1559,76 @@ impl<'a> InputStreams<'a> {
         true
     }
 
+    /// Were all of the buttons in the `chord` pressed on this exact frame?
+    ///
+    /// Unlike [`all_buttons_pressed`](Self::all_buttons_pressed), a member that was already held on an earlier frame prevents this from firing.
+    /// Inputs without edge detection (such as axes) are never considered just pressed: see [`press_kind`](Self::press_kind).
+    /// Returns `false` for an empty `chord`.
+    #[must_use]
+    pub fn chord_just_pressed(&self, chord: &PetitSet<InputKind, 8>) -> bool {
+        !chord.is_empty()
+            && chord
+                .iter()
+                .all(|button| self.press_kind(button) == PressKind::JustPressed)
+    }
+
+    /// Are all of the buttons in the `chord` pressed, with no other relevant buttons pressed?
+    ///
+    /// The relevant buttons are those of the same kinds as the members of the `chord`:
+    /// if the chord contains a [`KeyCode`], any other pressed [`KeyCode`] means that the chord is not exact,
+    /// and likewise for [`ScanCode`]s, [`MouseButton`]s and the gamepad buttons of each gamepad that is read.
+    /// Other kinds of inputs, such as axes, can only be checked for being pressed.
+    #[must_use]
+    pub fn chord_is_exact(&self, chord: &PetitSet<InputKind, 8>) -> bool {
+        if !self.all_buttons_pressed(chord) {
+            return false;
+        }
+
+        let contains_kind = |matches_kind: fn(&InputKind) -> bool| chord.iter().any(matches_kind);
+
+        if contains_kind(|button| matches!(button, InputKind::Keyboard(_)))
+            && self
+                .keycode
+                .get_pressed()
+                .any(|&keycode| !chord.contains(&InputKind::Keyboard(keycode)))
+        {
+            return false;
+        }
+
+        if contains_kind(|button| matches!(button, InputKind::KeyLocation(_)))
+            && self
+                .scan_codes
+                .get_pressed()
+                .any(|&scan_code| !chord.contains(&InputKind::KeyLocation(scan_code)))
+        {
+            return false;
+        }
+
+        if contains_kind(|button| matches!(button, InputKind::Mouse(_)))
+            && self.mouse_button.map_or(false, |mouse_buttons| {
+                mouse_buttons
+                    .get_pressed()
+                    .any(|&mouse_button| !chord.contains(&InputKind::Mouse(mouse_button)))
+            })
+        {
+            return false;
+        }
+
+        if contains_kind(|button| matches!(button, InputKind::GamepadButton(_))) {
+            let gamepads = self.gamepads_to_read();
+            let extra_gamepad_button = self.gamepad_buttons.get_pressed().any(|gamepad_button| {
+                gamepads.contains(&gamepad_button.gamepad)
+                    && !chord.contains(&InputKind::GamepadButton(gamepad_button.button_type))
+            });
+
+            if extra_gamepad_button {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Get the "value" of the input.
     ///
     /// For binary inputs such as buttons, this will always be either `0.0` or `1.0`. For analog
@@ -228,10 +1640,54 @@ impl<'a> InputStreams<'a> {
     /// # Warning
     ///
     /// If you need to ensure that this value is always in the range `[-1., 1.]`,
-    /// be sure to clamp the reutrned data.
+    /// be sure to clamp the reutrned data, or use [`input_value_clamped`](Self::input_value_clamped).
+    /// Enable the `debug_input_warnings` feature to log a warning in debug builds
+    /// when the value exceeds the [`value_warning_bound`](Self::value_warning_bound).
+    ///
+    /// If the value is non-zero, any hook registered for the `input` in the [`InputMatchHooks`] is run.
     pub fn input_value(&self, input: &UserInput) -> f32 {
+        let value = self.input_value_without_hooks(input);
+
+        if value != 0.0 {
+            self.run_input_match_hook(input, || value);
+        }
+
+        value
+    }
+
+    /// The value of the `input`, without running any hooks
+    ///
+    /// This is used where the value is read after [`input_pressed`](Self::input_pressed) has already run the hook,
+    /// such as in [`InputMap::which_pressed`](crate::input_map::InputMap::which_pressed), so that each hook runs once per frame.
+    pub(crate) fn input_value_without_hooks(&self, input: &UserInput) -> f32 {
+        let value = self.unchecked_input_value(input);
+
+        #[cfg(all(debug_assertions, feature = "debug_input_warnings"))]
+        self.warn_if_out_of_bounds(input, value);
+
+        value
+    }
+
+    /// Logs a warning if the magnitude of the `value` of the `input` exceeds the [`value_warning_bound`](Self::value_warning_bound)
+    ///
+    /// Returns `true` if a warning was logged.
+    #[cfg(all(debug_assertions, feature = "debug_input_warnings"))]
+    pub fn warn_if_out_of_bounds(&self, input: &UserInput, value: f32) -> bool {
+        if value.abs() > self.value_warning_bound {
+            bevy::log::warn!(
+                "The value of {input} was {value}, outside of [-{bound}, {bound}]. Consider clamping this input.",
+                bound = self.value_warning_bound
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The value of the `input`, without any debug checks or hooks
+    pub(crate) fn unchecked_input_value(&self, input: &UserInput) -> f32 {
         let use_button_value = || -> f32 {
-            if self.input_pressed(input) {
+            if self.unchecked_input_pressed(input) {
                 1.0
             } else {
                 0.0
@@ -240,112 +1696,287 @@ impl<'a> InputStreams<'a> {
 
         // Helper that takes the value returned by an axis and returns 0.0 if it is not within the
         // triggering range.
+        // Values within the triggering range are boosted to at least the anti-deadzone,
+        // then snapped to the quantization levels of the axis, if any.
+        // Absolute axes report only the magnitude of the value.
         let value_in_axis_range = |axis: &SingleAxis, value: f32| -> f32 {
             if value >= axis.negative_low && value <= axis.positive_low {
                 0.0
             } else {
-                value
+                let inner_zone = if value > 0.0 {
+                    axis.positive_low
+                } else {
+                    -axis.negative_low
+                };
+                let outer_zone = axis.outer_zone.unwrap_or(1.0);
+
+                // How far along the ramp from the dead zone to the outer zone the value is
+                let ramp = if outer_zone > inner_zone {
+                    ((value.abs() - inner_zone) / (outer_zone - inner_zone)).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+
+                let magnitude = match axis.deadzone_curve {
+                    DeadzoneCurve::Hard if axis.outer_zone.is_none() => value.abs(),
+                    DeadzoneCurve::Hard | DeadzoneCurve::Linear => ramp,
+                    DeadzoneCurve::Smoothstep => ramp * ramp * (3.0 - 2.0 * ramp),
+                };
+
+                let magnitude = magnitude.max(axis.anti_deadzone);
+                let magnitude = match axis.quantize {
+                    Some(steps) if steps >= 2 => {
+                        let step_size = 1.0 / (steps - 1) as f32;
+                        (magnitude / step_size).round() * step_size
+                    }
+                    _ => magnitude,
+                };
+
+                if axis.absolute {
+                    magnitude
+                } else {
+                    value.signum() * magnitude
+                }
             }
         };
 
         match input {
-            UserInput::Single(InputKind::SingleAxis(single_axis)) => {
-                match single_axis.axis_type {
-                    AxisType::Gamepad(axis_type) => {
-                        if let Some(gamepad) = self.guess_gamepad() {
-                            let value = self
-                                .gamepad_axes
-                                .get(GamepadAxis { gamepad, axis_type })
-                                .unwrap_or_default();
-
-                            value_in_axis_range(single_axis, value)
-                        } else {
-                            0.0
-                        }
-                    }
-                    AxisType::MouseWheel(axis_type) => {
-                        let mut total_mouse_wheel_movement = 0.0;
-                        // FIXME: verify that this works and doesn't double count events
-                        let mut event_reader = self.mouse_wheel.get_reader();
-
-                        // Arbitary scale to make line & pixel events more similar
-                        const PIXELS_PER_LINE: f32 = 14.0;
-
-                        for mouse_wheel_event in event_reader.iter(self.mouse_wheel) {
-                            total_mouse_wheel_movement += match axis_type {
-                                MouseWheelAxisType::X => mouse_wheel_event.x,
-                                MouseWheelAxisType::Y => mouse_wheel_event.y,
-                            } * match mouse_wheel_event.unit {
-                                MouseScrollUnit::Line => PIXELS_PER_LINE,
-                                MouseScrollUnit::Pixel => 1.0,
-                            };
-                        }
-                        value_in_axis_range(single_axis, total_mouse_wheel_movement)
-                    }
-                    // CLEANUP: deduplicate code with MouseWheel
-                    AxisType::MouseMotion(axis_type) => {
-                        let mut total_mouse_motion_movement = 0.0;
-                        // FIXME: verify that this works and doesn't double count events
-                        let mut event_reader = self.mouse_motion.get_reader();
-
-                        for mouse_motion_event in event_reader.iter(self.mouse_motion) {
-                            total_mouse_motion_movement += match axis_type {
-                                MouseMotionAxisType::X => mouse_motion_event.delta.x,
-                                MouseMotionAxisType::Y => mouse_motion_event.delta.y,
+            UserInput::Single(InputKind::SingleAxis(single_axis)) => match single_axis.axis_type {
+                AxisType::Gamepad(axis_type) => {
+                    let processed_value = |gamepad: Gamepad, raw_value: f32| -> f32 {
+                        let gamepad_axis = GamepadAxis { gamepad, axis_type };
+                        let raw_value = match self.gamepad_calibration {
+                            Some(gamepad_calibration) => {
+                                gamepad_calibration.apply(gamepad_axis, raw_value)
                             }
+                            None => raw_value,
+                        };
+
+                        match self
+                            .gamepad_profiles
+                            .and_then(|gamepad_profiles| gamepad_profiles.get(gamepad))
+                        {
+                            Some(axis_profile) => axis_profile.apply(raw_value),
+                            None => raw_value,
                         }
-                        value_in_axis_range(single_axis, total_mouse_motion_movement)
-                    }
+                    };
+
+                    let gamepads_to_read = self.gamepads_to_read();
+                    let connected_values = gamepads_to_read.iter().map(|&gamepad| {
+                        let gamepad_axis = GamepadAxis { gamepad, axis_type };
+                        processed_value(
+                            gamepad,
+                            self.gamepad_axes.get(gamepad_axis).unwrap_or_default(),
+                        )
+                    });
+                    // Disconnected gamepads hold their last-known position during their grace period
+                    let graced_values =
+                        self.graced_gamepad_states()
+                            .into_iter()
+                            .map(|(gamepad, gamepad_state)| {
+                                processed_value(
+                                    gamepad,
+                                    gamepad_state
+                                        .axes
+                                        .get(&axis_type)
+                                        .copied()
+                                        .unwrap_or_default(),
+                                )
+                            });
+
+                    let value = strongest_value(connected_values.chain(graced_values));
+
+                    value_in_axis_range(single_axis, value)
                 }
-            }
-            UserInput::Single(InputKind::DualAxis(_)) => {
-                self.input_axis_pair(input).unwrap_or_default().length()
-            }
-            UserInput::VirtualDPad { .. } => {
-                self.input_axis_pair(input).unwrap_or_default().length()
-            }
+                AxisType::MouseWheel(axis_type) => {
+                    value_in_axis_range(single_axis, self.mouse_wheel_axis(axis_type))
+                }
+                AxisType::MouseMotion(axis_type) => {
+                    let mouse_motion_value = self.mouse_motion_value();
+                    let value = match axis_type {
+                        MouseMotionAxisType::X => mouse_motion_value.x,
+                        MouseMotionAxisType::Y => mouse_motion_value.y,
+                    };
+                    value_in_axis_range(single_axis, value)
+                }
+            },
+            UserInput::Single(InputKind::DualAxis(_)) => self
+                .unchecked_input_axis_pair(input)
+                .unwrap_or_default()
+                .length(),
+            UserInput::VirtualDPad { .. } => self
+                .unchecked_input_axis_pair(input)
+                .unwrap_or_default()
+                .length(),
             // This is required because upstream bevy::input still waffles about whether triggers are buttons or axes
+            // Some controllers also report pressure for their bumpers, so this applies to every button:
+            // buttons without analog data fall back to their binary value
+            UserInput::Single(InputKind::GamepadButton(GamepadButtonType::Mode))
+                if !self.allow_guide_button =>
+            {
+                0.0
+            }
             UserInput::Single(InputKind::GamepadButton(button_type)) => {
-                if let Some(gamepad) = self.guess_gamepad() {
-                    // Get the value from the registered gamepad
+                let button_type = self.remapped_gamepad_button(*button_type);
+
+                // Get the value from the registered gamepads
+                let gamepads_to_read = self.gamepads_to_read();
+                let connected_values = gamepads_to_read.iter().map(|&gamepad| {
                     self.gamepad_button_axes
                         .get(GamepadButton {
                             gamepad,
-                            button_type: *button_type,
+                            button_type,
                         })
                         .unwrap_or_else(use_button_value)
-                } else {
-                    0.0
+                });
+                // Disconnected gamepads only record which buttons were pressed
+                let graced_values =
+                    self.graced_gamepad_states()
+                        .into_iter()
+                        .map(|(_, gamepad_state)| {
+                            if gamepad_state.pressed_buttons.contains(&button_type) {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        });
+
+                strongest_value(connected_values.chain(graced_values))
+            }
+            UserInput::Single(InputKind::Keyboard(keycode)) => {
+                let blocked = self.text_input_active
+                    || (self.external_focus
+                        && self
+                            .external_focus_inputs
+                            .contains(&InputKind::Keyboard(*keycode)));
+
+                match self
+                    .analog_keyboard
+                    .and_then(|analog_keyboard| analog_keyboard.get(*keycode))
+                {
+                    Some(_) if blocked => 0.0,
+                    Some(pressure) => pressure,
+                    None => use_button_value(),
                 }
             }
+            UserInput::Charge { input, max } => self
+                .input_history
+                .and_then(|input_history| input_history.charge(*input, self.guess_gamepad(), *max))
+                .unwrap_or_default(),
+            UserInput::Alias(_) => match self.resolve_alias(input) {
+                Some(aliased_input) => self.unchecked_input_value(aliased_input),
+                None => 0.0,
+            },
+            UserInput::Preferred(inputs) => match self.preferred_input(inputs) {
+                Some(preferred_input) => self.unchecked_input_value(preferred_input),
+                None => 0.0,
+            },
+            UserInput::Pinch => self
+                .input_history
+                .map(|input_history| input_history.pinch_delta())
+                .unwrap_or_default(),
+            UserInput::ScrollWhileHeld { button } => self.scroll_while_held(*button).y,
+            UserInput::Custom(custom_input) => custom_input.value(self),
             _ => use_button_value(),
         }
     }
 
+    /// The mean value of the `input` over the most recent `frames` frames
+    ///
+    /// This is a simple moving average of the values recorded in the [`InputHistory`],
+    /// which only records inputs that are bound in an [`InputMap`](crate::input_map::InputMap),
+    /// and stores at most [`MAX_VALUE_SAMPLES`](crate::input_history::MAX_VALUE_SAMPLES) frames.
+    /// If no values have been recorded, the current [`input_value`](Self::input_value) is returned instead.
+    #[must_use]
+    pub fn input_value_averaged(&self, input: &UserInput, frames: usize) -> f32 {
+        self.input_history
+            .and_then(|input_history| {
+                input_history.averaged_value(&self.gamepads_to_read(), input, frames)
+            })
+            .unwrap_or_else(|| self.input_value(input))
+    }
+
+    /// The [`input_value`](Self::input_value) of the `input` as a whole percentage, for display
+    ///
+    /// The value is rounded to the nearest percent, and clamped to `[-100, 100]`.
+    #[must_use]
+    pub fn input_percent(&self, input: &UserInput) -> i32 {
+        ((self.input_value(input) * 100.0).round() as i32).clamp(-100, 100)
+    }
+
+    /// Is the magnitude of the value of the `input` greater than `threshold`?
+    ///
+    /// The sign of the value is ignored, so an axis pushed past the threshold in either direction exceeds it.
+    #[must_use]
+    pub fn input_exceeds(&self, input: &UserInput, threshold: f32) -> bool {
+        self.input_value(input).abs() > threshold
+    }
+
+    /// Is the value of the `input` rising, falling or steady compared to the previous frame?
+    ///
+    /// The previous value is read from the [`InputHistory`], which only records inputs that are bound in an [`InputMap`](crate::input_map::InputMap).
+    /// If no previous value was recorded, this returns [`Trend::Steady`].
+    #[must_use]
+    pub fn input_trend(&self, input: &UserInput) -> Trend {
+        let previous_value = match self
+            .input_history
+            .and_then(|input_history| input_history.previous_value(&self.gamepads_to_read(), input))
+        {
+            Some(previous_value) => previous_value,
+            None => return Trend::Steady,
+        };
+
+        let delta = self.input_value(input) - previous_value;
+        if delta > TREND_EPSILON {
+            Trend::Rising
+        } else if delta < -TREND_EPSILON {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
+
     /// Get the axis pair associated to the user input.
     ///
-    /// If `input` is not a [`DualAxis`] or [`VirtualDPad`], returns [`None`].
+    /// If `input` is not a [`DualAxis`], [`VirtualDPad`] or [`InputKind::TouchFinger`], returns [`None`].
+    /// Touches also return [`None`] if there are not enough active touches.
     ///
     /// See [`ActionState::action_axis_pair()`] for usage.
     ///
     /// # Warning
     ///
     /// If you need to ensure that this value is always in the range `[-1., 1.]`,
-    /// be sure to clamp the returned data.
+    /// be sure to clamp the returned data, or use [`input_axis_pair_clamped`](Self::input_axis_pair_clamped).
+    /// Enable the `debug_input_warnings` feature to log a warning in debug builds
+    /// when the length of the axis pair exceeds the [`value_warning_bound`](Self::value_warning_bound).
     pub fn input_axis_pair(&self, input: &UserInput) -> Option<DualAxisData> {
+        let axis_pair = self.unchecked_input_axis_pair(input);
+
+        #[cfg(all(debug_assertions, feature = "debug_input_warnings"))]
+        {
+            if let Some(axis_pair) = axis_pair {
+                self.warn_if_out_of_bounds(input, axis_pair.length());
+            }
+        }
+
+        axis_pair
+    }
+
+    /// The axis pair of the `input`, without any debug checks
+    fn unchecked_input_axis_pair(&self, input: &UserInput) -> Option<DualAxisData> {
         match input {
             UserInput::Single(InputKind::DualAxis(dual_axis)) => {
-                let x = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.x)));
-                let y = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.y)));
+                // Each axis has already been zeroed within its own dead zone, and rescaled outside of it
+                let x = self
+                    .unchecked_input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.x)));
+                let y = self
+                    .unchecked_input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.y)));
 
-                if x > dual_axis.x.positive_low
-                    || x < dual_axis.x.negative_low
-                    || y > dual_axis.y.positive_low
-                    || y < dual_axis.y.negative_low
-                {
-                    Some(DualAxisData::new(x, y))
+                let axis_pair = DualAxisData::new(x, y);
+                if dual_axis.square_output {
+                    Some(axis_pair.circle_to_square())
                 } else {
-                    Some(DualAxisData::new(0.0, 0.0))
+                    Some(axis_pair)
                 }
             }
             UserInput::VirtualDPad(VirtualDPad {
@@ -353,16 +1984,414 @@ impl<'a> InputStreams<'a> {
                 down,
                 left,
                 right,
+                ramp,
             }) => {
-                let x = self.input_value(&UserInput::Single(*right)).abs()
-                    - self.input_value(&UserInput::Single(*left)).abs();
-                let y = self.input_value(&UserInput::Single(*up)).abs()
-                    - self.input_value(&UserInput::Single(*down)).abs();
-                Some(DualAxisData::new(x, y))
+                // Digital members ramp up over `ramp`, so that they can stand in for analog inputs
+                let member_value = |member: &InputKind| -> f32 {
+                    let value = self
+                        .unchecked_input_value(&UserInput::Single(*member))
+                        .abs();
+                    match (ramp, member) {
+                        (_, InputKind::SingleAxis(_) | InputKind::DualAxis(_)) | (None, _) => value,
+                        (Some(ramp), _) => value * self.hold_progress(member, *ramp),
+                    }
+                };
+                Some(DualAxisData::new(
+                    self.digital_analog_mode
+                        .combine(member_value(right), member_value(left)),
+                    self.digital_analog_mode
+                        .combine(member_value(up), member_value(down)),
+                ))
+            }
+            UserInput::Single(InputKind::TouchFinger { index }) => self
+                .touch_by_index(*index)
+                .map(|touch| DualAxisData::from_xy(touch.position())),
+            UserInput::Alias(_) => self
+                .resolve_alias(input)
+                .and_then(|aliased_input| self.unchecked_input_axis_pair(aliased_input)),
+            UserInput::Preferred(inputs) => self
+                .preferred_input(inputs)
+                .and_then(|preferred_input| self.unchecked_input_axis_pair(preferred_input)),
+            UserInput::ScrollWhileHeld { button } => {
+                Some(DualAxisData::from_xy(self.scroll_while_held(*button)))
             }
             _ => None,
         }
     }
+
+    /// Does the live input match the recorded `step`?
+    ///
+    /// The step matches if the [`input_value`](Self::input_value) of each recorded input
+    /// is within `tolerance` of its recorded value.
+    /// Inputs that were not recorded in the `step` are ignored.
+    #[must_use]
+    pub fn matches_step(&self, step: &InputFrame, tolerance: f32) -> bool {
+        step.values
+            .iter()
+            .all(|(input, value)| (self.input_value(input) - value).abs() <= tolerance)
+    }
+
+    /// The [`input_value`](Self::input_value) of the `input`, clamped to the range `[-1., 1.]`
+    ///
+    /// Unbounded inputs, such as mouse motion, can otherwise report values far outside of this range.
+    #[must_use]
+    pub fn input_value_clamped(&self, input: &UserInput) -> f32 {
+        self.input_value(input).clamp(-1.0, 1.0)
+    }
+
+    /// The [`input_axis_pair`](Self::input_axis_pair) of the `input`, clamped to the unit circle
+    ///
+    /// The direction of the axis pair is preserved, but its length is capped at `1.0`.
+    #[must_use]
+    pub fn input_axis_pair_clamped(&self, input: &UserInput) -> Option<DualAxisData> {
+        self.input_axis_pair(input).map(|mut axis_pair| {
+            axis_pair.clamp_length(1.0);
+            axis_pair
+        })
+    }
+
+    /// The compass heading of the `input`'s axis pair, in degrees
+    ///
+    /// Headings are measured clockwise from straight up, and range from `0.0` up to (but not including) `360.0`,
+    /// so pushing a stick to the right gives a heading of `90.0`.
+    /// The heading does not depend on how far the input is pushed.
+    ///
+    /// Returns `None` if the input has no axis pair, or if it is within its deadzone.
+    #[must_use]
+    pub fn stick_heading_degrees(&self, input: &UserInput) -> Option<f32> {
+        let xy = self.input_axis_pair(input)?.xy();
+        if xy == Vec2::ZERO {
+            return None;
+        }
+
+        Some(xy.x.atan2(xy.y).to_degrees().rem_euclid(360.0))
+    }
+
+    /// The overall intensity of movement across all of the provided `inputs`
+    ///
+    /// The axis pairs of the `inputs` are summed, and the length of the result is clamped to `1.0`.
+    /// Inputs without an axis pair, such as single buttons, are ignored:
+    /// use a [`VirtualDPad`] to include buttons in the movement.
+    #[must_use]
+    pub fn movement_intensity<'b>(&self, inputs: impl Iterator<Item = &'b UserInput>) -> f32 {
+        let total_movement: Vec2 = inputs
+            .filter_map(|input| self.input_axis_pair(input))
+            .map(|axis_pair| axis_pair.xy())
+            .sum();
+
+        total_movement.length().min(1.0)
+    }
+
+    /// Get the angle in radians between the axis pairs of the inputs `a` and `b`.
+    ///
+    /// The returned angle is always in the range `[0, PI]`.
+    ///
+    /// Returns [`None`] if either input is not a [`DualAxis`] or [`VirtualDPad`],
+    /// or if either axis pair has a length of zero.
+    #[must_use]
+    pub fn axis_pair_angle(&self, a: &UserInput, b: &UserInput) -> Option<f32> {
+        let a = self.input_axis_pair(a)?.xy();
+        let b = self.input_axis_pair(b)?.xy();
+
+        if a.length_squared() == 0.0 || b.length_squared() == 0.0 {
+            return None;
+        }
+
+        let angle = a.angle_between(b).abs();
+        if angle.is_finite() {
+            Some(angle)
+        } else {
+            None
+        }
+    }
+
+    /// The normalized sum of the axis pairs of all of the provided `inputs`
+    ///
+    /// This is useful for combining several movement inputs (such as a [`VirtualDPad`] and a [`DualAxis`]) into a single direction.
+    /// Inputs without an axis pair are ignored.
+    /// If the inputs cancel out, or none of them have an axis pair, the result is `(0, 0)`.
+    #[must_use]
+    pub fn combined_axis_pair<'b>(
+        &self,
+        inputs: impl Iterator<Item = &'b UserInput>,
+    ) -> DualAxisData {
+        let sum: Vec2 = inputs
+            .filter_map(|input| self.input_axis_pair(input))
+            .map(|axis_pair| axis_pair.xy())
+            .sum();
+
+        DualAxisData::from_xy(sum.normalize_or_zero())
+    }
+
+    /// The direction of the axis pair of the `input`, as a unit vector
+    ///
+    /// Returns [`None`] if the `input` has no axis pair, or if the axis pair is zero (such as when it is in its dead zone).
+    #[must_use]
+    pub fn input_direction(&self, input: &UserInput) -> Option<Vec2> {
+        self.input_axis_pair(input)?.xy().try_normalize()
+    }
+
+    /// The direction of the axis pair of the `input`, snapped to the nearest of `directions` evenly-spaced directions
+    ///
+    /// The first direction points along the positive x axis, with the rest spaced counterclockwise from it:
+    /// 4 directions snap to the cardinal directions, and 8 directions also include the diagonals.
+    /// Returns [`None`] if the `input` has no direction (see [`input_direction`](Self::input_direction)), or if `directions` is 0.
+    #[must_use]
+    pub fn input_snapped(&self, input: &UserInput, directions: u32) -> Option<Vec2> {
+        if directions == 0 {
+            return None;
+        }
+
+        let direction = self.input_direction(input)?;
+        let step = std::f32::consts::TAU / directions as f32;
+        let snapped_angle = (direction.y.atan2(direction.x) / step).round() * step;
+
+        Some(Vec2::new(snapped_angle.cos(), snapped_angle.sin()))
+    }
+
+    /// The absolute angle of the axis pair of the `input`, in radians, for "flick stick" style direct facing control
+    ///
+    /// Angles are measured counterclockwise from the positive x axis, in the range `-PI..=PI`,
+    /// so pointing the stick up and to the right returns `PI / 4`.
+    /// Unlike relative aiming, this does not depend on any previous stick position.
+    /// Returns [`None`] if the `input` has no direction (see [`input_direction`](Self::input_direction)),
+    /// such as when the stick is in its dead zone.
+    #[must_use]
+    pub fn flick_stick_angle(&self, input: &UserInput) -> Option<f32> {
+        let direction = self.input_direction(input)?;
+
+        Some(direction.y.atan2(direction.x))
+    }
+
+    /// The direction of the left stick of the gamepad, as a unit vector
+    ///
+    /// Uses the [`DEFAULT_DEADZONE`](DualAxis::DEFAULT_DEADZONE) of [`DualAxis::left_stick`],
+    /// and returns [`None`] when the stick is at rest.
+    #[must_use]
+    pub fn left_stick_direction(&self) -> Option<Vec2> {
+        self.input_direction(&DualAxis::left_stick().into())
+    }
+
+    /// The direction of the right stick of the gamepad, as a unit vector
+    ///
+    /// Uses the [`DEFAULT_DEADZONE`](DualAxis::DEFAULT_DEADZONE) of [`DualAxis::right_stick`],
+    /// and returns [`None`] when the stick is at rest.
+    #[must_use]
+    pub fn right_stick_direction(&self) -> Option<Vec2> {
+        self.input_direction(&DualAxis::right_stick().into())
+    }
+
+    /// The pressed state of each direction of the `dpad`, packed into a bitmask
+    ///
+    /// Each pressed direction sets its corresponding bit:
+    /// [`UP_BIT`](VirtualDPad::UP_BIT), [`DOWN_BIT`](VirtualDPad::DOWN_BIT),
+    /// [`LEFT_BIT`](VirtualDPad::LEFT_BIT) or [`RIGHT_BIT`](VirtualDPad::RIGHT_BIT).
+    /// Opposing directions are not cancelled out.
+    #[must_use]
+    pub fn dpad_bitmask(&self, dpad: &VirtualDPad) -> u8 {
+        [
+            (dpad.up, VirtualDPad::UP_BIT),
+            (dpad.down, VirtualDPad::DOWN_BIT),
+            (dpad.left, VirtualDPad::LEFT_BIT),
+            (dpad.right, VirtualDPad::RIGHT_BIT),
+        ]
+        .into_iter()
+        .filter(|&(button, _)| self.button_pressed(button))
+        .fold(0, |bitmask, (_, bit)| bitmask | bit)
+    }
+}
+
+/// The number of events of each type stored in an [`InputStreams`]
+///
+/// Returned by [`InputStreams::event_counts`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InputEventCounts {
+    /// The number of [`MouseMotion`] events
+    pub mouse_motion: usize,
+    /// The number of [`MouseWheel`] events
+    pub mouse_wheel: usize,
+}
+
+/// Which [`MouseWheel`] events are used when both line-based and pixel-based events arrive in the same frame
+///
+/// Mouse wheels typically send line-based events, while trackpads send pixel-based events.
+/// Set via [`InputStreams::scroll_source_priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollSourcePriority {
+    /// Both kinds of events are converted to pixels and summed
+    Sum,
+    /// Only pixel-based events are used
+    PreferPixel,
+    /// Only line-based events are used
+    PreferLine,
+}
+
+impl Default for ScrollSourcePriority {
+    fn default() -> Self {
+        ScrollSourcePriority::Sum
+    }
+}
+
+/// Where the pressed state of keyboard keys is read from
+///
+/// The polled [`Input`] resources are only updated once per frame,
+/// so can briefly disagree with [`KeyboardInput`] events that have been sent since.
+/// Set via [`InputStreams::keyboard_state_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyboardStateSource {
+    /// Keys are read from the [`Input<KeyCode>`] and [`Input<ScanCode>`] resources
+    Polled,
+    /// Keys are read from the most recent [`KeyboardInput`] event for that key,
+    /// falling back to the polled resources for keys without any events in the stream
+    Events,
+}
+
+impl Default for KeyboardStateSource {
+    fn default() -> Self {
+        KeyboardStateSource::Polled
+    }
+}
+
+/// How the opposing directions of each [`VirtualDPad`] axis are combined into a single value
+///
+/// This matters when a digital input (such as a key, with a value of 1) is mixed with an analog input (such as a stick).
+/// Set via [`InputStreams::digital_analog_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigitalAnalogMode {
+    /// The value of the negative direction is subtracted from the value of the positive direction
+    Sum,
+    /// The direction with the larger magnitude wins, and directions with equal magnitudes cancel out
+    MaxMagnitude,
+}
+
+impl Default for DigitalAnalogMode {
+    fn default() -> Self {
+        DigitalAnalogMode::Sum
+    }
+}
+
+impl DigitalAnalogMode {
+    /// Combines the magnitudes of the `positive` and `negative` directions of an axis
+    #[must_use]
+    pub fn combine(self, positive: f32, negative: f32) -> f32 {
+        match self {
+            DigitalAnalogMode::Sum => positive - negative,
+            DigitalAnalogMode::MaxMagnitude if positive > negative => positive,
+            DigitalAnalogMode::MaxMagnitude if negative > positive => -negative,
+            DigitalAnalogMode::MaxMagnitude => 0.0,
+        }
+    }
+}
+
+/// The order in which buttons are checked by [`InputStreams::first_gamepad_press`]
+///
+/// [`GamepadButtonType::Other`] buttons are not checked.
+pub const GAMEPAD_BUTTON_ORDER: [GamepadButtonType; 19] = [
+    GamepadButtonType::South,
+    GamepadButtonType::East,
+    GamepadButtonType::North,
+    GamepadButtonType::West,
+    GamepadButtonType::C,
+    GamepadButtonType::Z,
+    GamepadButtonType::LeftTrigger,
+    GamepadButtonType::LeftTrigger2,
+    GamepadButtonType::RightTrigger,
+    GamepadButtonType::RightTrigger2,
+    GamepadButtonType::Select,
+    GamepadButtonType::Start,
+    GamepadButtonType::Mode,
+    GamepadButtonType::LeftThumb,
+    GamepadButtonType::RightThumb,
+    GamepadButtonType::DPadUp,
+    GamepadButtonType::DPadDown,
+    GamepadButtonType::DPadLeft,
+    GamepadButtonType::DPadRight,
+];
+
+/// Every standard [`GamepadAxisType`]
+///
+/// [`GamepadAxisType::Other`] axes are not included.
+pub const GAMEPAD_AXIS_TYPES: [GamepadAxisType; 8] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::LeftZ,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+    GamepadAxisType::RightZ,
+    GamepadAxisType::DPadX,
+    GamepadAxisType::DPadY,
+];
+
+/// The direction in which the value of an input is changing
+///
+/// Returned by [`InputStreams::input_trend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trend {
+    /// The value has increased since the previous frame
+    Rising,
+    /// The value has decreased since the previous frame
+    Falling,
+    /// The value has changed by no more than [`TREND_EPSILON`] since the previous frame
+    Steady,
+}
+
+/// The largest change in value that is still considered [`Trend::Steady`]
+pub const TREND_EPSILON: f32 = 0.001;
+
+/// How a button is being pressed on the current frame
+///
+/// Returned by [`InputStreams::press_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PressKind {
+    /// The button is not pressed, and was not released this frame
+    None,
+    /// The button was pressed this frame
+    JustPressed,
+    /// The button was pressed on an earlier frame, and is still held
+    Held,
+    /// The button was released this frame
+    Released,
+}
+
+/// Returns the value with the largest magnitude, or `0.0` if there are no `values`
+fn strongest_value(values: impl Iterator<Item = f32>) -> f32 {
+    values.fold(0.0, |strongest, value| {
+        if value.abs() > strongest.abs() {
+            value
+        } else {
+            strongest
+        }
+    })
+}
+
+/// The [`PressKind`] of the `button` in the `input` stream
+fn button_press_kind<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+    input: &Input<T>,
+    button: T,
+) -> PressKind {
+    if input.just_pressed(button) {
+        PressKind::JustPressed
+    } else if input.pressed(button) {
+        PressKind::Held
+    } else if input.just_released(button) {
+        PressKind::Released
+    } else {
+        PressKind::None
+    }
+}
+
+/// The number of buttons in the `input` stream that were just pressed or just released
+fn button_change_count<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+    input: &Input<T>,
+) -> usize {
+    input.get_just_pressed().count() + input.get_just_released().count()
+}
+
+/// Was any button in the `input` stream just pressed or just released?
+fn button_changed<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+    input: &Input<T>,
+) -> bool {
+    input.get_just_pressed().next().is_some() || input.get_just_released().next().is_some()
 }
 
 /// A mutable collection of [`Input`] structs, which can be used for mocking user inputs.
@@ -384,6 +2413,8 @@ pub struct MutableInputStreams<'a> {
 
     /// A [`KeyCode`] [`Input`] stream
     pub keycode: &'a mut Input<KeyCode>,
+    /// A [`ScanCode`] [`Input`] stream
+    pub scan_codes: &'a mut Input<ScanCode>,
     /// Events used for mocking keyboard-related inputs
     pub keyboard_events: &'a mut Events<KeyboardInput>,
 
@@ -395,6 +2426,37 @@ pub struct MutableInputStreams<'a> {
     pub mouse_wheel: &'a mut Events<MouseWheel>,
     /// A [`MouseMotion`] event stream
     pub mouse_motion: &'a mut Events<MouseMotion>,
+    /// A [`Touches`] stream
+    pub touches: &'a mut Touches,
+
+    /// The [`InputHistory`] used to evaluate stateful inputs, if any
+    pub input_history: Option<&'a InputHistory>,
+    /// The [`InputAlias`] registry used to resolve [`UserInput::Alias`] inputs, if any
+    pub input_aliases: Option<&'a InputAlias>,
+    /// The [`InputMatchHooks`] run when inputs match, if any
+    pub input_match_hooks: Option<&'a InputMatchHooks>,
+    /// The [`MouseSampleRate`] measured by the plugin, if any
+    pub mouse_sample_rate: Option<&'a MouseSampleRate>,
+    /// The [`GamepadProfiles`] applied to raw gamepad axis values, if any
+    pub gamepad_profiles: Option<&'a GamepadProfiles>,
+    /// The [`GamepadCalibration`] recorded by [`calibrate_gamepad_center`](Self::calibrate_gamepad_center), if any
+    pub gamepad_calibration: Option<&'a mut GamepadCalibration>,
+    /// The [`GamepadButtonRemap`] applied to gamepad buttons before they are matched, if any
+    pub gamepad_button_remap: Option<&'a GamepadButtonRemap>,
+    /// The [`DisconnectGrace`] for which disconnected gamepads keep their last-known state, if any
+    pub disconnect_grace: Option<&'a DisconnectGrace>,
+    /// The [`ActiveContexts`] checked by [`input_pressed_in_context`](InputStreams::input_pressed_in_context), if any
+    pub active_contexts: Option<&'a ActiveContexts>,
+    /// The [`AnalogKeyboard`] pressures read for keyboard keys, if any
+    pub analog_keyboard: Option<&'a AnalogKeyboard>,
+    /// Does a text field have focus?
+    ///
+    /// While this is `true`, keyboard inputs are never pressed. Read from the [`TextInputActive`] resource.
+    pub text_input_active: bool,
+    /// Is input focus held by something outside of the game? Read from the [`ExternalFocus`] resource.
+    pub external_focus: bool,
+    /// The [`InputSettings`] copied into each [`InputStreams`] created from this struct, if any
+    pub input_settings: Option<&'a InputSettings>,
 
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
@@ -410,11 +2472,29 @@ impl<'a> MutableInputStreams<'a> {
             ResMut<Gamepads>,
             ResMut<Events<GamepadEventRaw>>,
             ResMut<Input<KeyCode>>,
+            ResMut<Input<ScanCode>>,
             ResMut<Events<KeyboardInput>>,
             ResMut<Input<MouseButton>>,
             ResMut<Events<MouseButtonInput>>,
             ResMut<Events<MouseWheel>>,
             ResMut<Events<MouseMotion>>,
+            ResMut<Touches>,
+            // These are grouped to stay within the maximum number of system parameters
+            (
+                Option<Res<InputHistory>>,
+                Option<Res<InputAlias>>,
+                Option<Res<InputMatchHooks>>,
+                Option<Res<MouseSampleRate>>,
+                Option<Res<GamepadProfiles>>,
+                Option<ResMut<GamepadCalibration>>,
+                Option<Res<GamepadButtonRemap>>,
+                Option<Res<DisconnectGrace>>,
+                Option<Res<ActiveContexts>>,
+                Option<Res<AnalogKeyboard>>,
+                Option<Res<TextInputActive>>,
+                Option<Res<ExternalFocus>>,
+                Option<Res<InputSettings>>,
+            ),
         )> = SystemState::new(world);
 
         let (
@@ -424,11 +2504,28 @@ impl<'a> MutableInputStreams<'a> {
             gamepads,
             gamepad_events,
             keyboard,
+            scan_codes,
             keyboard_events,
             mouse,
             mouse_button_events,
             mouse_wheel,
             mouse_motion,
+            touches,
+            (
+                input_history,
+                input_aliases,
+                input_match_hooks,
+                mouse_sample_rate,
+                gamepad_profiles,
+                gamepad_calibration,
+                gamepad_button_remap,
+                disconnect_grace,
+                active_contexts,
+                analog_keyboard,
+                text_input_active,
+                external_focus,
+                input_settings,
+            ),
         ) = input_system_state.get_mut(world);
 
         MutableInputStreams {
@@ -438,11 +2535,36 @@ impl<'a> MutableInputStreams<'a> {
             gamepads: gamepads.into_inner(),
             gamepad_events: gamepad_events.into_inner(),
             keycode: keyboard.into_inner(),
+            scan_codes: scan_codes.into_inner(),
             keyboard_events: keyboard_events.into_inner(),
             mouse_button: mouse.into_inner(),
             mouse_button_events: mouse_button_events.into_inner(),
             mouse_wheel: mouse_wheel.into_inner(),
             mouse_motion: mouse_motion.into_inner(),
+            touches: touches.into_inner(),
+            input_history: input_history.map(|input_history| input_history.into_inner()),
+            input_aliases: input_aliases.map(|input_aliases| input_aliases.into_inner()),
+            input_match_hooks: input_match_hooks
+                .map(|input_match_hooks| input_match_hooks.into_inner()),
+            mouse_sample_rate: mouse_sample_rate
+                .map(|mouse_sample_rate| mouse_sample_rate.into_inner()),
+            gamepad_profiles: gamepad_profiles
+                .map(|gamepad_profiles| gamepad_profiles.into_inner()),
+            gamepad_calibration: gamepad_calibration
+                .map(|gamepad_calibration| gamepad_calibration.into_inner()),
+            gamepad_button_remap: gamepad_button_remap
+                .map(|gamepad_button_remap| gamepad_button_remap.into_inner()),
+            disconnect_grace: disconnect_grace
+                .map(|disconnect_grace| disconnect_grace.into_inner()),
+            active_contexts: active_contexts.map(|active_contexts| active_contexts.into_inner()),
+            analog_keyboard: analog_keyboard.map(|analog_keyboard| analog_keyboard.into_inner()),
+            text_input_active: text_input_active
+                .map(|text_input_active| text_input_active.0)
+                .unwrap_or_default(),
+            external_focus: external_focus
+                .map(|external_focus| external_focus.0)
+                .unwrap_or_default(),
+            input_settings: input_settings.map(|input_settings| input_settings.into_inner()),
             associated_gamepad: gamepad,
         }
     }
@@ -450,17 +2572,52 @@ impl<'a> MutableInputStreams<'a> {
     /// Guess which registered [`Gamepad`] should be used.
     ///
     /// If an associated gamepad is set, use that.
-    /// Otherwise use the first registered gamepad, if any.
+    /// Otherwise use the registered gamepad with the lowest id, if any.
     pub fn guess_gamepad(&self) -> Option<Gamepad> {
         match self.associated_gamepad {
             Some(gamepad) => Some(gamepad),
-            None => self.gamepads.iter().next().copied(),
+            None => self
+                .gamepads
+                .iter()
+                .min_by_key(|gamepad| gamepad.id)
+                .copied(),
+        }
+    }
+
+    /// Records the current position of every standard gamepad axis as its resting position in the [`GamepadCalibration`]
+    ///
+    /// From then on, [`InputStreams::input_value`] reports gamepad axes relative to these positions,
+    /// cancelling out any stick drift present at the time of calibration.
+    /// If an associated gamepad is set, only that gamepad is calibrated.
+    ///
+    /// Does nothing if the [`GamepadCalibration`] resource does not exist.
+    pub fn calibrate_gamepad_center(&mut self) {
+        let gamepad_calibration = match &mut self.gamepad_calibration {
+            Some(gamepad_calibration) => gamepad_calibration,
+            None => return,
+        };
+
+        let gamepads: Vec<Gamepad> = match self.associated_gamepad {
+            Some(gamepad) => vec![gamepad],
+            None => self.gamepads.iter().copied().collect(),
+        };
+
+        for gamepad in gamepads {
+            for axis_type in GAMEPAD_AXIS_TYPES {
+                let gamepad_axis = GamepadAxis { gamepad, axis_type };
+
+                if let Some(center) = self.gamepad_axes.get(gamepad_axis) {
+                    gamepad_calibration.insert(gamepad_axis, center);
+                }
+            }
         }
     }
 }
 
 impl<'a> From<MutableInputStreams<'a>> for InputStreams<'a> {
     fn from(mutable_streams: MutableInputStreams<'a>) -> Self {
+        let input_settings = mutable_streams.input_settings.copied().unwrap_or_default();
+
         InputStreams {
             // This absurd-looking &*(foo) pattern convinces the compiler
             // that we want a reference to the underlying data with the correct lifetime
@@ -469,16 +2626,50 @@ impl<'a> From<MutableInputStreams<'a>> for InputStreams<'a> {
             gamepad_axes: &*(mutable_streams.gamepad_axes),
             gamepads: &*(mutable_streams.gamepads),
             keycode: &*(mutable_streams.keycode),
-            mouse_button: &*(mutable_streams.mouse_button),
+            scan_codes: &*(mutable_streams.scan_codes),
+            keyboard_events: &*(mutable_streams.keyboard_events),
+            mouse_button: Some(&*(mutable_streams.mouse_button)),
             mouse_wheel: &*(mutable_streams.mouse_wheel),
             mouse_motion: &*(mutable_streams.mouse_motion),
+            touches: &*(mutable_streams.touches),
+            input_history: mutable_streams.input_history,
+            input_aliases: mutable_streams.input_aliases,
+            input_match_hooks: mutable_streams.input_match_hooks,
+            mouse_sample_rate: mutable_streams.mouse_sample_rate,
+            gamepad_profiles: mutable_streams.gamepad_profiles,
+            gamepad_calibration: mutable_streams
+                .gamepad_calibration
+                .map(|gamepad_calibration| &*gamepad_calibration),
+            gamepad_button_remap: mutable_streams.gamepad_button_remap,
+            disconnect_grace: mutable_streams.disconnect_grace,
+            active_contexts: mutable_streams.active_contexts,
+            analog_keyboard: mutable_streams.analog_keyboard,
+            text_input_active: mutable_streams.text_input_active,
+            external_focus: mutable_streams.external_focus,
             associated_gamepad: mutable_streams.associated_gamepad,
+            associated_gamepads: PetitSet::default(),
+            max_delta_per_event: input_settings.max_delta_per_event,
+            scroll_threshold: input_settings.scroll_threshold,
+            scroll_source_priority: input_settings.scroll_source_priority,
+            max_scroll_per_frame: input_settings.max_scroll_per_frame,
+            pixels_per_line: input_settings.pixels_per_line,
+            value_warning_bound: input_settings.value_warning_bound,
+            digital_analog_mode: input_settings.digital_analog_mode,
+            keyboard_state_source: input_settings.keyboard_state_source,
+            external_focus_inputs: PetitSet::default(),
+            remote_input: RemoteInput(false),
+            mouse_motion_mode: input_settings.mouse_motion_mode,
+            cursor_grabbed: true,
+            cursor_position: None,
+            allow_guide_button: input_settings.allow_guide_button,
         }
     }
 }
 
 impl<'a> From<&'a MutableInputStreams<'a>> for InputStreams<'a> {
     fn from(mutable_streams: &'a MutableInputStreams<'a>) -> Self {
+        let input_settings = mutable_streams.input_settings.copied().unwrap_or_default();
+
         InputStreams {
             // This absurd-looking &*(foo) pattern convinces the compiler
             // that we want a reference to the underlying data with the correct lifetime
@@ -487,10 +2678,40 @@ impl<'a> From<&'a MutableInputStreams<'a>> for InputStreams<'a> {
             gamepad_axes: &*(mutable_streams.gamepad_axes),
             gamepads: &*(mutable_streams.gamepads),
             keycode: &*(mutable_streams.keycode),
-            mouse_button: &*(mutable_streams.mouse_button),
+            scan_codes: &*(mutable_streams.scan_codes),
+            keyboard_events: &*(mutable_streams.keyboard_events),
+            mouse_button: Some(&*(mutable_streams.mouse_button)),
             mouse_wheel: &*(mutable_streams.mouse_wheel),
             mouse_motion: &*(mutable_streams.mouse_motion),
+            touches: &*(mutable_streams.touches),
+            input_history: mutable_streams.input_history,
+            input_aliases: mutable_streams.input_aliases,
+            input_match_hooks: mutable_streams.input_match_hooks,
+            mouse_sample_rate: mutable_streams.mouse_sample_rate,
+            gamepad_profiles: mutable_streams.gamepad_profiles,
+            gamepad_calibration: mutable_streams.gamepad_calibration.as_deref(),
+            gamepad_button_remap: mutable_streams.gamepad_button_remap,
+            disconnect_grace: mutable_streams.disconnect_grace,
+            active_contexts: mutable_streams.active_contexts,
+            analog_keyboard: mutable_streams.analog_keyboard,
+            text_input_active: mutable_streams.text_input_active,
+            external_focus: mutable_streams.external_focus,
             associated_gamepad: mutable_streams.associated_gamepad,
+            associated_gamepads: PetitSet::default(),
+            max_delta_per_event: input_settings.max_delta_per_event,
+            scroll_threshold: input_settings.scroll_threshold,
+            scroll_source_priority: input_settings.scroll_source_priority,
+            max_scroll_per_frame: input_settings.max_scroll_per_frame,
+            pixels_per_line: input_settings.pixels_per_line,
+            value_warning_bound: input_settings.value_warning_bound,
+            digital_analog_mode: input_settings.digital_analog_mode,
+            keyboard_state_source: input_settings.keyboard_state_source,
+            external_focus_inputs: PetitSet::default(),
+            remote_input: RemoteInput(false),
+            mouse_motion_mode: input_settings.mouse_motion_mode,
+            cursor_grabbed: true,
+            cursor_position: None,
+            allow_guide_button: input_settings.allow_guide_button,
         }
     }
 }
@@ -14,8 +14,15 @@ pub mod buttonlike;
 pub mod clashing_inputs;
 mod display_impl;
 pub mod errors;
+pub mod gamepad_profiles;
+pub mod input_alias;
+pub mod input_context;
+pub mod input_history;
+pub mod input_hooks;
+pub mod input_macro;
 pub mod input_map;
 pub mod input_mocking;
+pub mod input_snapshot;
 pub mod input_streams;
 pub mod orientation;
 pub mod plugin;
@@ -28,15 +35,18 @@ pub use leafwing_input_manager_macros::Actionlike;
 /// Everything you need to get started
 pub mod prelude {
     pub use crate::action_state::{ActionState, ActionStateDriver};
-    pub use crate::axislike::{DualAxis, MouseWheelAxisType, SingleAxis, VirtualDPad};
+    pub use crate::axislike::{
+        AxisCross, CircleGesture, DeadzoneCurve, DualAxis, MouseWheelAxisType, SingleAxis,
+        StickOpposition, VirtualDPad,
+    };
     pub use crate::buttonlike::MouseWheelDirection;
     pub use crate::clashing_inputs::ClashStrategy;
-    pub use crate::input_map::InputMap;
+    pub use crate::input_map::{InputMap, KeyResolution};
     pub use crate::input_mocking::MockInput;
-    pub use crate::user_input::UserInput;
+    pub use crate::user_input::{InputCategory, InputKindMask, UserInput};
 
     pub use crate::plugin::InputManagerPlugin;
-    pub use crate::plugin::ToggleActions;
+    pub use crate::plugin::{ExternalFocus, TextInputActive, ToggleActions};
     pub use crate::{Actionlike, InputManagerBundle};
 }
 
@@ -1,6 +1,14 @@
 //! Contains main plugin exported by this crate.
 
 use crate::clashing_inputs::ClashStrategy;
+use crate::gamepad_profiles::{
+    DisconnectGrace, GamepadButtonRemap, GamepadCalibration, GamepadProfiles,
+};
+use crate::input_alias::InputAlias;
+use crate::input_context::ActiveContexts;
+use crate::input_history::{InputHistory, MouseSampleRate};
+use crate::input_hooks::InputMatchHooks;
+use crate::input_streams::{AnalogKeyboard, InputSettings};
 use crate::Actionlike;
 use core::hash::Hash;
 use core::marker::PhantomData;
@@ -38,6 +46,14 @@ use bevy::ui::UiSystem;
 ///
 /// - [`tick_action_state`](crate::systems::tick_action_state), which resets the `pressed` and `just_pressed` fields of the [`ActionState`](crate::action_state::ActionState) each frame
 ///     - labeled [`InputManagerSystem::Reset`]
+/// - [`update_input_history`](crate::systems::update_input_history), which records button presses into the [`InputHistory`] resource to power stateful inputs
+///     - labeled [`InputManagerSystem::History`]
+///     - only added once, no matter how many copies of this plugin are added
+/// - [`update_mouse_sample_rate`](crate::systems::update_mouse_sample_rate), which measures the rate of mouse motion events in the [`MouseSampleRate`] resource
+///     - labeled [`InputManagerSystem::History`]
+///     - only added once, no matter how many copies of this plugin are added
+/// - [`record_input_values`](crate::systems::record_input_values), which records the value of each bound input into the [`InputHistory`]
+///     - an exclusive system, which runs at the end of [`CoreStage::PreUpdate`]
 /// - [`update_action_state`](crate::systems::update_action_state), which collects [`Input`](bevy::input::Input) resources to update the [`ActionState`](crate::action_state::ActionState)
 ///     - labeled [`InputManagerSystem::Update`]
 /// - [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction), for triggering actions from buttons
@@ -106,8 +122,34 @@ impl<A: Actionlike> Plugin for InputManagerPlugin<A> {
                         .label(InputManagerSystem::ReleaseOnDisable)
                         .after(InputManagerSystem::Update),
                 )
+                .add_system_to_stage(
+                    CoreStage::PreUpdate,
+                    record_input_values::<A>.exclusive_system().at_end(),
+                )
                 .add_system_to_stage(CoreStage::PostUpdate, release_on_input_map_removed::<A>);
 
+                // The input history is shared between all action types, so it should only be recorded once
+                // This is tracked separately from the history itself, which may have been inserted by the user
+                if !app.world.contains_resource::<InputHistorySystemsAdded>() {
+                    app.insert_resource(InputHistorySystemsAdded)
+                        .init_resource::<InputHistory>()
+                        .init_resource::<MouseSampleRate>()
+                        .add_system_to_stage(
+                            CoreStage::PreUpdate,
+                            update_input_history
+                                .label(InputManagerSystem::History)
+                                .after(InputSystem)
+                                .before(InputManagerSystem::Update),
+                        )
+                        .add_system_to_stage(
+                            CoreStage::PreUpdate,
+                            update_mouse_sample_rate
+                                .label(InputManagerSystem::History)
+                                .after(InputSystem)
+                                .before(InputManagerSystem::Update),
+                        );
+                }
+
                 #[cfg(feature = "ui")]
                 app.add_system_to_stage(
                     CoreStage::PreUpdate,
@@ -136,7 +178,18 @@ impl<A: Actionlike> Plugin for InputManagerPlugin<A> {
 
         // Resources
         app.init_resource::<ToggleActions<A>>()
-            .init_resource::<ClashStrategy>();
+            .init_resource::<ClashStrategy>()
+            .init_resource::<InputAlias>()
+            .init_resource::<InputMatchHooks>()
+            .init_resource::<GamepadProfiles>()
+            .init_resource::<GamepadCalibration>()
+            .init_resource::<GamepadButtonRemap>()
+            .init_resource::<DisconnectGrace>()
+            .init_resource::<ActiveContexts>()
+            .init_resource::<AnalogKeyboard>()
+            .init_resource::<TextInputActive>()
+            .init_resource::<ExternalFocus>()
+            .init_resource::<InputSettings>();
     }
 }
 
@@ -175,6 +228,29 @@ impl<A: Actionlike> Default for ToggleActions<A> {
     }
 }
 
+/// Does a text field currently have focus?
+///
+/// While this is `true`, keyboard inputs are ignored by every [`InputMap`](crate::input_map::InputMap),
+/// so that typing does not trigger gameplay actions.
+/// Mouse and gamepad inputs are unaffected.
+///
+/// If this resource does not exist, keyboard inputs work normally.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextInputActive(pub bool);
+
+/// Is input focus currently held by something outside of the game?
+///
+/// Set this to `true` while an overlay, chat window or other external UI is capturing input.
+/// While this is `true`, inputs marked with [`InputMap::set_respect_external_focus`](crate::input_map::InputMap::set_respect_external_focus)
+/// are never pressed. All other inputs continue to work normally.
+///
+/// If this resource does not exist, focus is treated as internal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalFocus(pub bool);
+
+/// Marks that the systems which record the shared [`InputHistory`] have been added to the app
+struct InputHistorySystemsAdded;
+
 /// [`SystemLabel`]s for the [`crate::systems`] used by this crate
 ///
 /// `Reset` must occur before `Update`
@@ -182,6 +258,8 @@ impl<A: Actionlike> Default for ToggleActions<A> {
 pub enum InputManagerSystem {
     /// Advances actions timers to clean up the state of the input manager and clear `just_pressed` and just_released`
     Tick,
+    /// Records the state of buttons across frames in the [`InputHistory`] resource
+    History,
     /// Collects input data to update the [`ActionState`](crate::action_state::ActionState)
     Update,
     /// Release all actions in all [`ActionState`](crate::action_state::ActionState)s if [`ToggleActions`](crate::plugin::ToggleActions) was added
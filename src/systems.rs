@@ -5,21 +5,31 @@ use crate::action_state::ActionStateDriver;
 use crate::{
     action_state::{ActionDiff, ActionState},
     clashing_inputs::ClashStrategy,
+    gamepad_profiles::{DisconnectGrace, GamepadButtonRemap, GamepadCalibration, GamepadProfiles},
+    input_alias::InputAlias,
+    input_context::ActiveContexts,
+    input_history::{InputHistory, MouseSampleRate},
     input_map::InputMap,
-    input_streams::InputStreams,
-    plugin::ToggleActions,
+    input_streams::{
+        primary_cursor_state, AnalogKeyboard, InputSettings, InputStreams, RemoteInput,
+    },
+    plugin::{ExternalFocus, TextInputActive, ToggleActions},
+    user_input::UserInput,
     Actionlike,
 };
 
 use bevy::ecs::{prelude::*, schedule::ShouldRun};
 use bevy::input::{
-    gamepad::{GamepadAxis, GamepadButton, Gamepads},
-    keyboard::KeyCode,
+    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventType, Gamepads},
+    keyboard::{KeyCode, KeyboardInput, ScanCode},
     mouse::{MouseButton, MouseMotion, MouseWheel},
+    touch::Touches,
     Axis, Input,
 };
+use bevy::math::Vec2;
 use bevy::time::Time;
 use bevy::utils::Instant;
+use bevy::window::Windows;
 
 #[cfg(feature = "ui")]
 use bevy::ui::Interaction;
@@ -64,22 +74,83 @@ pub fn update_action_state<A: Actionlike>(
     gamepad_axes: Res<Axis<GamepadAxis>>,
     gamepads: Res<Gamepads>,
     keycode: Res<Input<KeyCode>>,
-    mouse_button: Res<Input<MouseButton>>,
+    (scan_codes, keyboard_events): (Res<Input<ScanCode>>, Res<Events<KeyboardInput>>),
+    mouse_button: Option<Res<Input<MouseButton>>>,
     mouse_wheel: Res<Events<MouseWheel>>,
     mouse_motion: Res<Events<MouseMotion>>,
+    touches: Res<Touches>,
+    // These are grouped to stay within the maximum number of system parameters
+    (
+        input_history,
+        input_aliases,
+        input_match_hooks,
+        mouse_sample_rate,
+        gamepad_profiles,
+        gamepad_calibration,
+        gamepad_button_remap,
+        disconnect_grace,
+        active_contexts,
+        analog_keyboard,
+        text_input_active,
+        external_focus,
+        input_settings,
+        windows,
+    ): (
+        Option<Res<InputHistory>>,
+        Option<Res<InputAlias>>,
+        Option<Res<InputMatchHooks>>,
+        Option<Res<MouseSampleRate>>,
+        Option<Res<GamepadProfiles>>,
+        Option<Res<GamepadCalibration>>,
+        Option<Res<GamepadButtonRemap>>,
+        Option<Res<DisconnectGrace>>,
+        Option<Res<ActiveContexts>>,
+        Option<Res<AnalogKeyboard>>,
+        Option<Res<TextInputActive>>,
+        Option<Res<ExternalFocus>>,
+        Option<Res<InputSettings>>,
+        Option<Res<Windows>>,
+    ),
     clash_strategy: Res<ClashStrategy>,
     mut action_state: Option<ResMut<ActionState<A>>>,
     mut input_map: Option<ResMut<InputMap<A>>>,
-    mut query: Query<(&mut ActionState<A>, &InputMap<A>)>,
+    mut query: Query<(&mut ActionState<A>, &InputMap<A>, Option<&RemoteInput>)>,
 ) {
     let gamepad_buttons = gamepad_buttons.into_inner();
     let gamepad_button_axes = gamepad_button_axes.into_inner();
     let gamepad_axes = gamepad_axes.into_inner();
     let gamepads = gamepads.into_inner();
     let keycode = keycode.into_inner();
-    let mouse_button = mouse_button.into_inner();
+    let scan_codes = scan_codes.into_inner();
+    let keyboard_events = keyboard_events.into_inner();
+    let mouse_button = mouse_button.map(|mouse_button| mouse_button.into_inner());
     let mouse_wheel = mouse_wheel.into_inner();
     let mouse_motion = mouse_motion.into_inner();
+    let touches = touches.into_inner();
+    let input_history = input_history.map(|input_history| input_history.into_inner());
+    let input_aliases = input_aliases.map(|input_aliases| input_aliases.into_inner());
+    let input_match_hooks =
+        input_match_hooks.map(|input_match_hooks| input_match_hooks.into_inner());
+    let mouse_sample_rate =
+        mouse_sample_rate.map(|mouse_sample_rate| mouse_sample_rate.into_inner());
+    let gamepad_profiles = gamepad_profiles.map(|gamepad_profiles| gamepad_profiles.into_inner());
+    let gamepad_calibration =
+        gamepad_calibration.map(|gamepad_calibration| gamepad_calibration.into_inner());
+    let gamepad_button_remap =
+        gamepad_button_remap.map(|gamepad_button_remap| gamepad_button_remap.into_inner());
+    let disconnect_grace = disconnect_grace.map(|disconnect_grace| disconnect_grace.into_inner());
+    let active_contexts = active_contexts.map(|active_contexts| active_contexts.into_inner());
+    let analog_keyboard = analog_keyboard.map(|analog_keyboard| analog_keyboard.into_inner());
+    let text_input_active = text_input_active
+        .map(|text_input_active| text_input_active.0)
+        .unwrap_or_default();
+    let external_focus = external_focus
+        .map(|external_focus| external_focus.0)
+        .unwrap_or_default();
+    let input_settings = input_settings
+        .map(|input_settings| *input_settings)
+        .unwrap_or_default();
+    let (cursor_grabbed, cursor_position) = primary_cursor_state(windows.as_deref());
 
     if let (Some(input_map), Some(action_state)) = (&mut input_map, &mut action_state) {
         let input_streams = InputStreams {
@@ -88,32 +159,199 @@ pub fn update_action_state<A: Actionlike>(
             gamepad_axes,
             gamepads,
             keycode,
+            scan_codes,
+            keyboard_events,
             mouse_button,
             mouse_wheel,
             mouse_motion,
+            touches,
+            input_history,
+            input_aliases,
+            input_match_hooks,
+            mouse_sample_rate,
+            gamepad_profiles,
+            gamepad_calibration,
+            gamepad_button_remap,
+            disconnect_grace,
+            active_contexts,
+            analog_keyboard,
+            text_input_active,
+            external_focus,
             associated_gamepad: input_map.gamepad(),
+            associated_gamepads: input_map.gamepads().clone(),
+            max_delta_per_event: input_settings.max_delta_per_event,
+            scroll_threshold: input_settings.scroll_threshold,
+            scroll_source_priority: input_settings.scroll_source_priority,
+            max_scroll_per_frame: input_settings.max_scroll_per_frame,
+            pixels_per_line: input_settings.pixels_per_line,
+            value_warning_bound: input_settings.value_warning_bound,
+            digital_analog_mode: input_settings.digital_analog_mode,
+            keyboard_state_source: input_settings.keyboard_state_source,
+            external_focus_inputs: input_map.external_focus_inputs().clone(),
+            remote_input: RemoteInput(false),
+            mouse_motion_mode: input_settings.mouse_motion_mode,
+            cursor_grabbed,
+            cursor_position,
+            allow_guide_button: input_settings.allow_guide_button,
         };
 
         action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
     }
 
-    for (mut action_state, input_map) in query.iter_mut() {
+    for (mut action_state, input_map, remote_input) in query.iter_mut() {
         let input_streams = InputStreams {
             gamepad_buttons,
             gamepad_button_axes,
             gamepad_axes,
             gamepads,
             keycode,
+            scan_codes,
+            keyboard_events,
             mouse_button,
             mouse_wheel,
             mouse_motion,
+            touches,
+            input_history,
+            input_aliases,
+            input_match_hooks,
+            mouse_sample_rate,
+            gamepad_profiles,
+            gamepad_calibration,
+            gamepad_button_remap,
+            disconnect_grace,
+            active_contexts,
+            analog_keyboard,
+            text_input_active,
+            external_focus,
             associated_gamepad: input_map.gamepad(),
+            associated_gamepads: input_map.gamepads().clone(),
+            max_delta_per_event: input_settings.max_delta_per_event,
+            scroll_threshold: input_settings.scroll_threshold,
+            scroll_source_priority: input_settings.scroll_source_priority,
+            max_scroll_per_frame: input_settings.max_scroll_per_frame,
+            pixels_per_line: input_settings.pixels_per_line,
+            value_warning_bound: input_settings.value_warning_bound,
+            digital_analog_mode: input_settings.digital_analog_mode,
+            keyboard_state_source: input_settings.keyboard_state_source,
+            external_focus_inputs: input_map.external_focus_inputs().clone(),
+            remote_input: remote_input.copied().unwrap_or_default(),
+            mouse_motion_mode: input_settings.mouse_motion_mode,
+            cursor_grabbed,
+            cursor_position,
+            allow_guide_button: input_settings.allow_guide_button,
         };
 
         action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
     }
 }
 
+/// Records the buttons pressed this frame and the current gamepad axis values into the [`InputHistory`] resource
+///
+/// This powers stateful inputs, such as [`UserInput::Toggle`](crate::user_input::UserInput::Toggle).
+#[allow(clippy::too_many_arguments)]
+pub fn update_input_history(
+    mut input_history: ResMut<InputHistory>,
+    keycode: Res<Input<KeyCode>>,
+    scan_codes: Res<Input<ScanCode>>,
+    mouse_button: Option<Res<Input<MouseButton>>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut gamepad_events: EventReader<GamepadEvent>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    touches: Res<Touches>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    time: Res<Time>,
+) {
+    let current_instant = time.last_update().unwrap_or_else(|| time.startup());
+    // Without a mouse, no mouse buttons are ever pressed
+    let no_mouse_buttons = Input::<MouseButton>::default();
+    // Gamepads may still be registered on the frame that they disconnect
+    let disconnected: Vec<Gamepad> = gamepad_events
+        .iter()
+        .filter(|event| event.event_type == GamepadEventType::Disconnected)
+        .map(|event| event.gamepad)
+        .collect();
+
+    input_history.update(
+        &keycode,
+        &scan_codes,
+        mouse_button.as_deref().unwrap_or(&no_mouse_buttons),
+        &gamepad_buttons,
+        &gamepads,
+        &disconnected,
+        &gamepad_axes,
+        &touches,
+        current_instant,
+    );
+
+    let mouse_motion = mouse_motion
+        .iter()
+        .fold(Vec2::ZERO, |total, event| total + event.delta);
+    let mouse_wheel = mouse_wheel.iter().fold(Vec2::ZERO, |total, event| {
+        total + Vec2::new(event.x, event.y)
+    });
+    input_history.record_mouse_movement(mouse_motion, mouse_wheel);
+}
+
+/// Records the number of [`MouseMotion`] events received this frame into the [`MouseSampleRate`] resource
+pub fn update_mouse_sample_rate(
+    mut mouse_sample_rate: ResMut<MouseSampleRate>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    time: Res<Time>,
+) {
+    let current_instant = time.last_update().unwrap_or_else(|| time.startup());
+
+    mouse_sample_rate.record(current_instant, mouse_motion.iter().count());
+}
+
+/// Records the value of every input in each [`InputMap`] into the [`InputHistory`] resource
+///
+/// This is an exclusive system, and should run at the end of [`CoreStage::PreUpdate`](bevy::app::CoreStage::PreUpdate).
+/// These values are compared against on the next frame, powering [`InputStreams::input_trend`] and [`UserInput::AxisCross`].
+pub fn record_input_values<A: Actionlike>(world: &mut World) {
+    if !world.contains_resource::<InputHistory>() {
+        return;
+    }
+
+    let mut query = world.query::<&InputMap<A>>();
+    let mut values = Vec::new();
+
+    let world_ref: &World = world;
+    for input_map in query
+        .iter(world_ref)
+        .chain(world_ref.get_resource::<InputMap<A>>())
+    {
+        let mut input_streams = InputStreams::from_world(world_ref, input_map.gamepad());
+        input_streams.associated_gamepads = input_map.gamepads().clone();
+        // Values are kept apart for each set of gamepads, so that players do not share them
+        let gamepads = input_streams.gamepads_to_read();
+
+        // This is an internal pass, so it must not run any hooks a second time
+        for (inputs, _action) in input_map.iter() {
+            for input in inputs.iter() {
+                values.push((
+                    gamepads.clone(),
+                    input.clone(),
+                    input_streams.unchecked_input_value(input),
+                ));
+
+                // Crossings are detected by comparing against the raw axis value on the previous frame
+                if let UserInput::AxisCross(axis_cross) = input {
+                    let raw_axis: UserInput = axis_cross.raw_axis().into();
+                    let value = input_streams.unchecked_input_value(&raw_axis);
+                    values.push((gamepads.clone(), raw_axis, value));
+                }
+            }
+        }
+    }
+
+    let mut input_history = world.resource_mut::<InputHistory>();
+    for (gamepads, input, value) in values {
+        input_history.record_value(&gamepads, input, value);
+    }
+}
+
 /// When a button with a component of type `A` is clicked, press the corresponding action in the [`ActionState`]
 ///
 /// The action triggered is determined by the variant stored in your UI-defined button.
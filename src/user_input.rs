@@ -1,14 +1,26 @@
 //! Helpful abstractions over user inputs of all sorts
 
-use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode, mouse::MouseButton};
+use bevy::input::{
+    gamepad::GamepadButtonType,
+    keyboard::{KeyCode, ScanCode},
+    mouse::MouseButton,
+};
 
-use bevy::utils::HashSet;
+use bevy::utils::{Duration, HashSet};
 use petitset::PetitSet;
 use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::BitOr;
+use std::sync::Arc;
 
 use crate::{
-    axislike::{AxisType, DualAxis, SingleAxis, VirtualDPad},
+    axislike::{
+        AxisCross, AxisType, CircleGesture, DualAxis, SingleAxis, StickOpposition, VirtualDPad,
+    },
     buttonlike::{MouseMotionDirection, MouseWheelDirection},
+    input_alias::AliasId,
+    input_streams::InputStreams,
 };
 
 /// Some combination of user input, which may cross [`Input`]-mode boundaries
@@ -25,6 +37,132 @@ pub enum UserInput {
     Chord(PetitSet<InputKind, 8>),
     /// A virtual DPad that you can get an [`AxisPair`] from
     VirtualDPad(VirtualDPad),
+    /// A button that flips between pressed and released each time it is pressed
+    ///
+    /// The toggle state is read from the [`InputHistory`](crate::input_history::InputHistory) resource,
+    /// which is added by [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+    Toggle {
+        /// The button whose presses flip the toggle
+        input: InputKind,
+    },
+    /// A button that is charged while held, and fires when released
+    ///
+    /// On the frame the button is released, this input is pressed,
+    /// and its value is the duration it was held for divided by `max`, clamped to `[0.0, 1.0]`.
+    /// The hold durations are read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+    Charge {
+        /// The button that is held to charge the input
+        input: InputKind,
+        /// How long the button must be held to fully charge the input
+        max: Duration,
+    },
+    /// A button tapped while another button is held, such as holding a bumper and tapping a face button
+    ///
+    /// This input is only pressed on the frame that `tap` is pressed,
+    /// and only if `hold` was already pressed on an earlier frame and is still held.
+    HoldTap {
+        /// The button that must be held throughout
+        hold: InputKind,
+        /// The button that is tapped during the hold
+        tap: InputKind,
+    },
+    /// A sequence of buttons, each pressed while every earlier step is still held
+    ///
+    /// This is pressed once every step is held, as long as they were pressed in order.
+    /// Releasing an earlier step resets the progress from that step onwards,
+    /// as re-pressing it places it after the later steps.
+    /// Progress can be read with [`InputHistory::staircase_progress`](crate::input_history::InputHistory::staircase_progress).
+    Staircase {
+        /// The buttons that must be pressed, in order
+        steps: Vec<InputKind>,
+    },
+    /// An axis rapidly shaken back and forth, such as a stick waggled or a mouse jiggled
+    ///
+    /// This is pressed while the velocity of the `axis` has changed sign at least `reversals` times within `window`.
+    /// The axis history is read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+    Shake {
+        /// The axis that is shaken
+        axis: AxisType,
+        /// How many changes of direction are needed to trigger the input
+        reversals: u32,
+        /// How far back changes of direction are counted
+        window: Duration,
+    },
+    /// A button that ignores repeated presses within a cooldown
+    ///
+    /// A press is ignored if it begins within `cooldown` of the last press that was not ignored.
+    /// Once a press is accepted, this input is pressed for as long as the button is held.
+    /// The press times are read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+    Debounced {
+        /// The button that is debounced
+        input: InputKind,
+        /// How long after an accepted press further presses are ignored
+        cooldown: Duration,
+    },
+    /// A button tapped and then pressed again and held, such as double-tapping forward to sprint
+    ///
+    /// This is pressed for as long as the second press is held,
+    /// as long as it began within `tap_window` of the press before it.
+    /// The press times are read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+    DoubleTapHold {
+        /// The button that is tapped and then held
+        input: InputKind,
+        /// How soon after the first tap the second press must begin
+        tap_window: Duration,
+    },
+    /// A button that is only pressed on the frame that it is pressed, or on the frame that it is released
+    ///
+    /// This is useful for actions that fire on release, such as loosing an arrow.
+    /// Only buttons have edges: axis inputs are never pressed by this input.
+    OnEdge {
+        /// The button whose edges are detected
+        input: InputKind,
+        /// Which edge triggers the input
+        edge: TriggerEdge,
+    },
+    /// A reference to another input, registered in the [`InputAlias`](crate::input_alias::InputAlias) resource
+    ///
+    /// Aliases allow complex inputs such as chords to be named once and reused across many bindings.
+    Alias(AliasId),
+    /// A stick rotated around in a circle within a time window
+    ///
+    /// See [`CircleGesture`] for more details.
+    CircleGesture(CircleGesture),
+    /// Two touches being pinched together or spread apart
+    ///
+    /// The value of this input is the change in distance between the first two active touches this frame,
+    /// and is positive when the touches are spreading apart.
+    /// It is pressed whenever this value is not zero.
+    /// The touch distances are read from the [`InputHistory`](crate::input_history::InputHistory) resource.
+    Pinch,
+    /// An axis crossing a threshold, which is only pressed on the frame that it crosses
+    ///
+    /// See [`AxisCross`] for more details.
+    AxisCross(AxisCross),
+    /// Both gamepad sticks pushed in roughly opposite directions
+    ///
+    /// See [`StickOpposition`] for more details.
+    StickOpposition(StickOpposition),
+    /// The mouse wheel, which is only read while a mouse button is held
+    ///
+    /// Its value and axis pair are the scroll accumulated this frame while the `button` is held, and zero otherwise.
+    /// This is useful for actions such as zooming while the middle mouse button is held.
+    ScrollWhileHeld {
+        /// The mouse button that must be held for scrolling to be read
+        button: MouseButton,
+    },
+    /// The first of several inputs whose devices are available, such as a gamepad stick with a keyboard fallback
+    ///
+    /// Each input is checked in order, and the first one that is
+    /// [satisfiable](crate::input_streams::InputStreams::input_satisfiable) is read.
+    /// Later inputs are ignored entirely, even if they are pressed.
+    Preferred(Vec<UserInput>),
+    /// An input defined by a closure, which inspects the [`InputStreams`] and returns a value
+    ///
+    /// See [`CustomInput`] for more details.
+    /// Closures cannot be serialized, so this variant is skipped by serde.
+    #[serde(skip)]
+    Custom(CustomInput),
 }
 
 impl UserInput {
@@ -52,11 +190,43 @@ impl UserInput {
     /// - A [`Single`][UserInput::Single] input returns 1
     /// - A [`Chord`][UserInput::Chord] returns the number of buttons in the chord
     /// - A [`VirtualDPad`][UserInput::VirtualDPad] returns 1
+    /// - A [`Toggle`][UserInput::Toggle] returns 1
+    /// - A [`Charge`][UserInput::Charge] returns 1
+    /// - A [`HoldTap`][UserInput::HoldTap] returns 2
+    /// - A [`Staircase`][UserInput::Staircase] returns the number of steps
+    /// - A [`Shake`][UserInput::Shake] returns 1
+    /// - A [`Debounced`][UserInput::Debounced] input returns 1
+    /// - A [`DoubleTapHold`][UserInput::DoubleTapHold] input returns 1
+    /// - An [`OnEdge`][UserInput::OnEdge] input returns 1
+    /// - An [`Alias`][UserInput::Alias] returns 1, as the aliased input cannot be looked up here
+    /// - A [`CircleGesture`][UserInput::CircleGesture] returns 1
+    /// - A [`Pinch`][UserInput::Pinch] returns 1
+    /// - An [`AxisCross`][UserInput::AxisCross] returns 1
+    /// - A [`StickOpposition`][UserInput::StickOpposition] returns 1
+    /// - A [`ScrollWhileHeld`][UserInput::ScrollWhileHeld] input returns 1
+    /// - A [`Preferred`][UserInput::Preferred] input returns the largest length of its inputs
+    /// - A [`Custom`][UserInput::Custom] input returns 1
     pub fn len(&self) -> usize {
         match self {
             UserInput::Single(_) => 1,
             UserInput::Chord(button_set) => button_set.len(),
             UserInput::VirtualDPad { .. } => 1,
+            UserInput::Toggle { .. } => 1,
+            UserInput::Charge { .. } => 1,
+            UserInput::HoldTap { .. } => 2,
+            UserInput::Staircase { steps } => steps.len(),
+            UserInput::Shake { .. } => 1,
+            UserInput::Debounced { .. } => 1,
+            UserInput::DoubleTapHold { .. } => 1,
+            UserInput::OnEdge { .. } => 1,
+            UserInput::Alias(_) => 1,
+            UserInput::CircleGesture(_) => 1,
+            UserInput::Pinch => 1,
+            UserInput::AxisCross(_) => 1,
+            UserInput::StickOpposition(_) => 1,
+            UserInput::ScrollWhileHeld { .. } => 1,
+            UserInput::Preferred(inputs) => inputs.iter().map(UserInput::len).max().unwrap_or(0),
+            UserInput::Custom(_) => 1,
         }
     }
 
@@ -84,7 +254,12 @@ impl UserInput {
     /// ```
     pub fn n_matching(&self, buttons: &HashSet<InputKind>) -> usize {
         match self {
-            UserInput::Single(button) => {
+            UserInput::Single(button)
+            | UserInput::Toggle { input: button }
+            | UserInput::Charge { input: button, .. }
+            | UserInput::Debounced { input: button, .. }
+            | UserInput::DoubleTapHold { input: button, .. }
+            | UserInput::OnEdge { input: button, .. } => {
                 if buttons.contains(button) {
                     1
                 } else {
@@ -106,6 +281,7 @@ impl UserInput {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 let mut n_matching = 0;
                 for button in buttons.iter() {
@@ -118,52 +294,149 @@ impl UserInput {
 
                 n_matching
             }
+            UserInput::Staircase { steps } => {
+                steps.iter().filter(|step| buttons.contains(*step)).count()
+            }
+            UserInput::HoldTap { hold, tap } => {
+                let mut n_matching = 0;
+                for button in [hold, tap] {
+                    if buttons.contains(button) {
+                        n_matching += 1;
+                    }
+                }
+
+                n_matching
+            }
+            UserInput::Shake { axis, .. } => {
+                if buttons.contains(&InputKind::SingleAxis(SingleAxis::symmetric(*axis, 0.0))) {
+                    1
+                } else {
+                    0
+                }
+            }
+            // The aliased input cannot be looked up here
+            UserInput::Alias(_) => 0,
+            UserInput::CircleGesture(CircleGesture { axis, .. }) => {
+                if buttons.contains(&InputKind::DualAxis(*axis)) {
+                    1
+                } else {
+                    0
+                }
+            }
+            UserInput::AxisCross(axis_cross) => {
+                if buttons.contains(&InputKind::SingleAxis(axis_cross.raw_axis())) {
+                    1
+                } else {
+                    0
+                }
+            }
+            UserInput::StickOpposition(_) => {
+                let mut n_matching = 0;
+                for stick in [DualAxis::left_stick(), DualAxis::right_stick()] {
+                    if buttons.contains(&InputKind::DualAxis(stick)) {
+                        n_matching += 1;
+                    }
+                }
+
+                n_matching
+            }
+            UserInput::ScrollWhileHeld { button } => {
+                if buttons.contains(&InputKind::Mouse(*button)) {
+                    1
+                } else {
+                    0
+                }
+            }
+            UserInput::Preferred(inputs) => inputs
+                .iter()
+                .map(|input| input.n_matching(buttons))
+                .max()
+                .unwrap_or(0),
+            // Custom inputs are opaque
+            UserInput::Pinch | UserInput::Custom(_) => 0,
+        }
+    }
+
+    /// Returns the [`InputKind`]s that this [`UserInput`] depends on
+    ///
+    /// [`UserInput::Alias`] inputs cannot be resolved here, and [`UserInput::Pinch`] and [`UserInput::Custom`] inputs
+    /// are not made up of [`InputKind`]s, so these return an empty list.
+    #[must_use]
+    pub fn input_kinds(&self) -> Vec<InputKind> {
+        match self {
+            UserInput::Single(button)
+            | UserInput::Toggle { input: button }
+            | UserInput::Charge { input: button, .. }
+            | UserInput::Debounced { input: button, .. }
+            | UserInput::DoubleTapHold { input: button, .. }
+            | UserInput::OnEdge { input: button, .. } => vec![*button],
+            UserInput::Chord(button_set) => button_set.iter().copied().collect(),
+            UserInput::VirtualDPad(VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+                ..
+            }) => vec![*up, *down, *left, *right],
+            UserInput::HoldTap { hold, tap } => vec![*hold, *tap],
+            UserInput::Staircase { steps } => steps.clone(),
+            UserInput::CircleGesture(CircleGesture { axis, .. }) => {
+                vec![InputKind::DualAxis(*axis)]
+            }
+            UserInput::AxisCross(axis_cross) => vec![InputKind::SingleAxis(axis_cross.raw_axis())],
+            UserInput::StickOpposition(_) => vec![
+                InputKind::DualAxis(DualAxis::left_stick()),
+                InputKind::DualAxis(DualAxis::right_stick()),
+            ],
+            UserInput::ScrollWhileHeld { button } => vec![
+                InputKind::Mouse(*button),
+                InputKind::DualAxis(DualAxis::mouse_wheel()),
+            ],
+            UserInput::Shake { axis, .. } => {
+                vec![InputKind::SingleAxis(SingleAxis::symmetric(*axis, 0.0))]
+            }
+            UserInput::Preferred(inputs) => {
+                inputs.iter().flat_map(UserInput::input_kinds).collect()
+            }
+            UserInput::Alias(_) | UserInput::Pinch | UserInput::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the smallest set of [`InputKind`]s that must be pressed for this [`UserInput`] to be pressed
+    ///
+    /// Chords need every member, while a [`VirtualDPad`] is pressed by any one of its directions,
+    /// so only its `up` input is returned.
+    /// A [`UserInput::Preferred`] returns the minimal inputs of its first input.
+    /// All other inputs need each of their [`input_kinds`](Self::input_kinds).
+    #[must_use]
+    pub fn minimal_inputs(&self) -> Vec<InputKind> {
+        match self {
+            UserInput::VirtualDPad(VirtualDPad { up, .. }) => vec![*up],
+            UserInput::Preferred(inputs) => inputs
+                .first()
+                .map(UserInput::minimal_inputs)
+                .unwrap_or_default(),
+            _ => self.input_kinds(),
         }
     }
 
     /// Returns the raw inputs that make up this [`UserInput`]
+    ///
+    /// [`UserInput::Alias`] inputs cannot be resolved without the [`InputAlias`](crate::input_alias::InputAlias) resource,
+    /// and so have no raw inputs.
     pub fn raw_inputs(&self) -> RawInputs {
         let mut raw_inputs = RawInputs::default();
 
         match self {
-            UserInput::Single(button) => match *button {
-                InputKind::DualAxis(dual_axis) => {
-                    raw_inputs
-                        .axis_data
-                        .push((dual_axis.x.axis_type, dual_axis.x.value));
-                    raw_inputs
-                        .axis_data
-                        .push((dual_axis.y.axis_type, dual_axis.y.value));
-                }
-                InputKind::SingleAxis(single_axis) => raw_inputs
-                    .axis_data
-                    .push((single_axis.axis_type, single_axis.value)),
-                InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
-                InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
-                InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
-                InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
-                InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
-            },
+            UserInput::Single(button)
+            | UserInput::Toggle { input: button }
+            | UserInput::Charge { input: button, .. }
+            | UserInput::Debounced { input: button, .. }
+            | UserInput::DoubleTapHold { input: button, .. }
+            | UserInput::OnEdge { input: button, .. } => raw_inputs.add_input_kind(*button),
             UserInput::Chord(button_set) => {
                 for button in button_set.iter() {
-                    match *button {
-                        InputKind::DualAxis(dual_axis) => {
-                            raw_inputs
-                                .axis_data
-                                .push((dual_axis.x.axis_type, dual_axis.x.value));
-                            raw_inputs
-                                .axis_data
-                                .push((dual_axis.y.axis_type, dual_axis.y.value));
-                        }
-                        InputKind::SingleAxis(single_axis) => raw_inputs
-                            .axis_data
-                            .push((single_axis.axis_type, single_axis.value)),
-                        InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
-                        InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
-                        InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
-                        InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
-                        InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
-                    }
+                    raw_inputs.add_input_kind(*button);
                 }
             }
             UserInput::VirtualDPad(VirtualDPad {
@@ -171,34 +444,68 @@ impl UserInput {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 for button in [up, down, left, right] {
-                    match *button {
-                        InputKind::DualAxis(dual_axis) => {
-                            raw_inputs
-                                .axis_data
-                                .push((dual_axis.x.axis_type, dual_axis.x.value));
-                            raw_inputs
-                                .axis_data
-                                .push((dual_axis.y.axis_type, dual_axis.y.value));
-                        }
-                        InputKind::SingleAxis(single_axis) => raw_inputs
-                            .axis_data
-                            .push((single_axis.axis_type, single_axis.value)),
-                        InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
-                        InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
-                        InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
-                        InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
-                        InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
-                    }
+                    raw_inputs.add_input_kind(*button);
                 }
             }
+            UserInput::Staircase { steps } => {
+                for step in steps {
+                    raw_inputs.add_input_kind(*step);
+                }
+            }
+            UserInput::HoldTap { hold, tap } => {
+                raw_inputs.add_input_kind(*hold);
+                raw_inputs.add_input_kind(*tap);
+            }
+            UserInput::Shake { axis, .. } => {
+                raw_inputs.add_input_kind(InputKind::SingleAxis(SingleAxis::symmetric(*axis, 0.0)))
+            }
+            UserInput::Alias(_) => (),
+            UserInput::CircleGesture(CircleGesture { axis, .. }) => {
+                raw_inputs.add_input_kind(InputKind::DualAxis(*axis))
+            }
+            // Touches cannot currently be mocked
+            UserInput::Pinch => (),
+            UserInput::AxisCross(axis_cross) => {
+                raw_inputs.add_input_kind(InputKind::SingleAxis(axis_cross.raw_axis()))
+            }
+            UserInput::StickOpposition(_) => {
+                raw_inputs.add_input_kind(InputKind::DualAxis(DualAxis::left_stick()));
+                raw_inputs.add_input_kind(InputKind::DualAxis(DualAxis::right_stick()));
+            }
+            UserInput::ScrollWhileHeld { button } => {
+                raw_inputs.add_input_kind(InputKind::Mouse(*button));
+                raw_inputs.add_input_kind(InputKind::DualAxis(DualAxis::mouse_wheel()));
+            }
+            UserInput::Preferred(inputs) => {
+                for button in inputs.iter().flat_map(UserInput::input_kinds) {
+                    raw_inputs.add_input_kind(button);
+                }
+            }
+            UserInput::Custom(_) => (),
         };
 
         raw_inputs
     }
 }
 
+/// Finds every input in `inputs` that contains the physical `candidate` input
+///
+/// This is static analysis of the bindings, and does not depend on the current input state.
+/// It is useful for binding editors, to warn when a single button is shared by several bindings.
+/// See [`UserInput::input_kinds`] for which inputs are considered.
+#[must_use]
+pub fn inputs_containing(
+    candidate: InputKind,
+    inputs: impl Iterator<Item = UserInput>,
+) -> Vec<UserInput> {
+    inputs
+        .filter(|input| input.input_kinds().contains(&candidate))
+        .collect()
+}
+
 impl From<InputKind> for UserInput {
     fn from(input: InputKind) -> Self {
         UserInput::Single(input)
@@ -217,6 +524,30 @@ impl From<SingleAxis> for UserInput {
     }
 }
 
+impl From<CustomInput> for UserInput {
+    fn from(input: CustomInput) -> Self {
+        UserInput::Custom(input)
+    }
+}
+
+impl From<StickOpposition> for UserInput {
+    fn from(input: StickOpposition) -> Self {
+        UserInput::StickOpposition(input)
+    }
+}
+
+impl From<AxisCross> for UserInput {
+    fn from(input: AxisCross) -> Self {
+        UserInput::AxisCross(input)
+    }
+}
+
+impl From<CircleGesture> for UserInput {
+    fn from(input: CircleGesture) -> Self {
+        UserInput::CircleGesture(input)
+    }
+}
+
 impl From<VirtualDPad> for UserInput {
     fn from(input: VirtualDPad) -> Self {
         UserInput::VirtualDPad(input)
@@ -235,6 +566,12 @@ impl From<KeyCode> for UserInput {
     }
 }
 
+impl From<ScanCode> for UserInput {
+    fn from(input: ScanCode) -> Self {
+        UserInput::Single(InputKind::KeyLocation(input))
+    }
+}
+
 impl From<MouseButton> for UserInput {
     fn from(input: MouseButton) -> Self {
         UserInput::Single(InputKind::Mouse(input))
@@ -272,12 +609,24 @@ pub enum InputKind {
     DualAxis(DualAxis),
     /// A button on a keyboard
     Keyboard(KeyCode),
+    /// A physical key on the keyboard, identified by its [`ScanCode`]
+    ///
+    /// Unlike [`InputKind::Keyboard`], this does not depend on the keyboard layout.
+    /// When both are bound to the same action, the [`KeyResolution`](crate::input_map::KeyResolution) of the [`InputMap`](crate::input_map::InputMap) decides which is used.
+    KeyLocation(ScanCode),
     /// A button on a mouse
     Mouse(MouseButton),
     /// A discretized mousewheel movement
     MouseWheel(MouseWheelDirection),
     /// A discretized mouse movement
     MouseMotion(MouseMotionDirection),
+    /// The `index`-th active touch, with touches ordered by their id
+    ///
+    /// This is pressed while the touch exists, and its axis pair is the position of the touch.
+    TouchFinger {
+        /// Which of the active touches to read, starting from 0
+        index: usize,
+    },
 }
 
 impl From<DualAxis> for InputKind {
@@ -304,6 +653,12 @@ impl From<KeyCode> for InputKind {
     }
 }
 
+impl From<ScanCode> for InputKind {
+    fn from(input: ScanCode) -> Self {
+        InputKind::KeyLocation(input)
+    }
+}
+
 impl From<MouseButton> for InputKind {
     fn from(input: MouseButton) -> Self {
         InputKind::Mouse(input)
@@ -322,6 +677,235 @@ impl From<MouseMotionDirection> for InputKind {
     }
 }
 
+/// A set of [`InputKind`] categories, used to restrict which inputs are read
+///
+/// Masks behave like bitflags, and can be combined with `|`.
+/// Used by [`InputStreams::input_pressed_filtered`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InputKindMask(u8);
+
+impl InputKindMask {
+    /// No categories
+    pub const NONE: InputKindMask = InputKindMask(0);
+    /// Keyboard keys, identified by either their [`KeyCode`] or their [`ScanCode`]
+    pub const KEYBOARD: InputKindMask = InputKindMask(1 << 0);
+    /// Mouse buttons, mouse wheel movement and mouse motion
+    pub const MOUSE: InputKindMask = InputKindMask(1 << 1);
+    /// Gamepad buttons and axes
+    pub const GAMEPAD: InputKindMask = InputKindMask(1 << 2);
+    /// Touches
+    pub const TOUCH: InputKindMask = InputKindMask(1 << 3);
+    /// Every category
+    pub const ALL: InputKindMask = InputKindMask(0b1111);
+
+    /// Does this mask include every category in `other`?
+    #[must_use]
+    pub const fn contains(self, other: InputKindMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The categories that the `input_kind` belongs to
+    #[must_use]
+    pub fn of(input_kind: &InputKind) -> InputKindMask {
+        match input_kind {
+            InputKind::GamepadButton(_) => InputKindMask::GAMEPAD,
+            InputKind::SingleAxis(axis) => InputKindMask::of_axis(axis.axis_type),
+            InputKind::DualAxis(axis) => {
+                InputKindMask::of_axis(axis.x.axis_type) | InputKindMask::of_axis(axis.y.axis_type)
+            }
+            InputKind::Keyboard(_) | InputKind::KeyLocation(_) => InputKindMask::KEYBOARD,
+            InputKind::Mouse(_) | InputKind::MouseWheel(_) | InputKind::MouseMotion(_) => {
+                InputKindMask::MOUSE
+            }
+            InputKind::TouchFinger { .. } => InputKindMask::TOUCH,
+        }
+    }
+
+    /// The category that the `axis_type` belongs to
+    #[must_use]
+    pub fn of_axis(axis_type: AxisType) -> InputKindMask {
+        match axis_type {
+            AxisType::Gamepad(_) => InputKindMask::GAMEPAD,
+            AxisType::MouseWheel(_) | AxisType::MouseMotion(_) => InputKindMask::MOUSE,
+        }
+    }
+}
+
+impl BitOr for InputKindMask {
+    type Output = InputKindMask;
+
+    fn bitor(self, rhs: InputKindMask) -> InputKindMask {
+        InputKindMask(self.0 | rhs.0)
+    }
+}
+
+/// A category of button-like input devices
+///
+/// Used by [`InputStreams::category_changed`](crate::input_streams::InputStreams::category_changed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputCategory {
+    /// Keyboard keys, identified by either their [`KeyCode`] or their [`ScanCode`]
+    Keyboard,
+    /// Mouse buttons
+    Mouse,
+    /// Gamepad buttons
+    Gamepad,
+}
+
+impl From<InputCategory> for InputKindMask {
+    fn from(category: InputCategory) -> Self {
+        match category {
+            InputCategory::Keyboard => InputKindMask::KEYBOARD,
+            InputCategory::Mouse => InputKindMask::MOUSE,
+            InputCategory::Gamepad => InputKindMask::GAMEPAD,
+        }
+    }
+}
+
+/// Which change in the state of a button triggers a [`UserInput::OnEdge`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TriggerEdge {
+    /// Triggered on the frame that the button is pressed
+    Press,
+    /// Triggered on the frame that the button is released
+    Release,
+}
+
+/// A keyboard modifier, which may be pressed using either the left or right variant of its key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    /// The Alt key, also known as Option on Mac OS
+    Alt,
+    /// The Control key
+    Control,
+    /// The Shift key
+    Shift,
+    /// The Windows key, also known as Command on Mac OS or Super on Linux
+    Win,
+}
+
+impl Modifier {
+    /// Every [`Modifier`], in declaration order
+    pub const ALL: [Modifier; 4] = [
+        Modifier::Alt,
+        Modifier::Control,
+        Modifier::Shift,
+        Modifier::Win,
+    ];
+
+    /// The left and right [`KeyCode`]s that correspond to this modifier
+    #[must_use]
+    pub fn keycodes(self) -> [KeyCode; 2] {
+        match self {
+            Modifier::Alt => [KeyCode::LAlt, KeyCode::RAlt],
+            Modifier::Control => [KeyCode::LControl, KeyCode::RControl],
+            Modifier::Shift => [KeyCode::LShift, KeyCode::RShift],
+            Modifier::Win => [KeyCode::LWin, KeyCode::RWin],
+        }
+    }
+}
+
+/// The chords intercepted by Windows, such as `Alt + F4`
+#[cfg(target_os = "windows")]
+const RESERVED_OS_CHORDS: &[(&[Modifier], KeyCode)] = &[
+    (&[Modifier::Alt], KeyCode::F4),
+    (&[Modifier::Alt], KeyCode::Tab),
+    (&[Modifier::Control, Modifier::Alt], KeyCode::Delete),
+    (&[Modifier::Win], KeyCode::L),
+    (&[Modifier::Win], KeyCode::D),
+];
+
+/// The chords intercepted by Mac OS, such as `Cmd + Q`
+#[cfg(target_os = "macos")]
+const RESERVED_OS_CHORDS: &[(&[Modifier], KeyCode)] = &[
+    (&[Modifier::Win], KeyCode::Q),
+    (&[Modifier::Win], KeyCode::Tab),
+    (&[Modifier::Win], KeyCode::Space),
+    (&[Modifier::Win], KeyCode::H),
+    (&[Modifier::Win, Modifier::Alt], KeyCode::Escape),
+];
+
+/// The chords intercepted by Linux, such as `Ctrl + Alt + Delete`
+#[cfg(target_os = "linux")]
+const RESERVED_OS_CHORDS: &[(&[Modifier], KeyCode)] =
+    &[(&[Modifier::Control, Modifier::Alt], KeyCode::Delete)];
+
+/// No chords are known to be intercepted on other platforms
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+const RESERVED_OS_CHORDS: &[(&[Modifier], KeyCode)] = &[];
+
+/// The chords that are intercepted by the operating system of the target platform, and so never reach the app
+///
+/// Each chord is a list of [`Modifier`]s, which may be pressed on either side of the keyboard, followed by a key.
+/// The list is deliberately conservative: window managers and user settings may reserve many more.
+/// Use [`InputStreams::is_os_reserved`] to check whether a binding includes one of these chords.
+#[must_use]
+pub fn reserved_os_chords() -> &'static [(&'static [Modifier], KeyCode)] {
+    RESERVED_OS_CHORDS
+}
+
+/// A closure that computes the value of a [`UserInput::Custom`] input from the [`InputStreams`]
+///
+/// The input is pressed whenever the closure returns a value other than `0.0`.
+/// Two [`CustomInput`]s are only equal if they share the same closure: clones are equal, but identical closures created separately are not.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::keyboard::KeyCode;
+/// use leafwing_input_manager::user_input::{CustomInput, UserInput};
+///
+/// let both_shifts: UserInput = CustomInput::new(|input_streams| {
+///     if input_streams.keycode.pressed(KeyCode::LShift) && input_streams.keycode.pressed(KeyCode::RShift) {
+///         1.0
+///     } else {
+///         0.0
+///     }
+/// })
+/// .into();
+///
+/// assert_eq!(both_shifts, both_shifts.clone());
+/// ```
+#[derive(Clone)]
+pub struct CustomInput(Arc<dyn Fn(&InputStreams) -> f32 + Send + Sync>);
+
+impl CustomInput {
+    /// Creates a new [`CustomInput`] from the provided closure
+    pub fn new(f: impl Fn(&InputStreams) -> f32 + Send + Sync + 'static) -> Self {
+        CustomInput(Arc::new(f))
+    }
+
+    /// Evaluates the closure on the provided `input_streams`
+    #[must_use]
+    pub fn value(&self, input_streams: &InputStreams) -> f32 {
+        (self.0)(input_streams)
+    }
+
+    /// The address of the shared closure, used for equality and hashing
+    fn address(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
+}
+
+impl Debug for CustomInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomInput({:p})", self.address())
+    }
+}
+
+impl PartialEq for CustomInput {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for CustomInput {}
+
+impl Hash for CustomInput {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
+
 /// The basic input events that make up a [`UserInput`].
 ///
 /// Obtained by calling [`UserInput::raw_inputs()`].
@@ -329,6 +913,8 @@ impl From<MouseMotionDirection> for InputKind {
 pub struct RawInputs {
     /// Physical keyboard buttons
     pub keycodes: Vec<KeyCode>,
+    /// Keyboard scan codes
+    pub scan_codes: Vec<ScanCode>,
     /// Mouse buttons
     pub mouse_buttons: Vec<MouseButton>,
     /// Discretized mouse wheel inputs
@@ -343,6 +929,31 @@ pub struct RawInputs {
     pub axis_data: Vec<(AxisType, Option<f32>)>,
 }
 
+impl RawInputs {
+    /// Adds the raw inputs that make up the provided `input_kind`
+    fn add_input_kind(&mut self, input_kind: InputKind) {
+        match input_kind {
+            InputKind::DualAxis(dual_axis) => {
+                self.axis_data
+                    .push((dual_axis.x.axis_type, dual_axis.x.value));
+                self.axis_data
+                    .push((dual_axis.y.axis_type, dual_axis.y.value));
+            }
+            InputKind::SingleAxis(single_axis) => self
+                .axis_data
+                .push((single_axis.axis_type, single_axis.value)),
+            InputKind::GamepadButton(button) => self.gamepad_buttons.push(button),
+            InputKind::Keyboard(button) => self.keycodes.push(button),
+            InputKind::KeyLocation(scan_code) => self.scan_codes.push(scan_code),
+            InputKind::Mouse(button) => self.mouse_buttons.push(button),
+            InputKind::MouseWheel(button) => self.mouse_wheel.push(button),
+            InputKind::MouseMotion(button) => self.mouse_motion.push(button),
+            // Touches cannot currently be mocked
+            InputKind::TouchFinger { .. } => (),
+        }
+    }
+}
+
 #[cfg(test)]
 impl RawInputs {
     fn from_keycode(keycode: KeyCode) -> RawInputs {
@@ -571,3 +1182,26 @@ mod raw_input_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod inputs_containing_tests {
+    use crate::user_input::{inputs_containing, InputKind, UserInput};
+    use bevy::input::keyboard::KeyCode;
+
+    #[test]
+    fn shared_modifier() {
+        let ctrl_s = UserInput::chord([KeyCode::LControl, KeyCode::S]);
+        let ctrl_z = UserInput::chord([KeyCode::LControl, KeyCode::Z]);
+        let alt_f4 = UserInput::chord([KeyCode::LAlt, KeyCode::F4]);
+        let inputs = [ctrl_s.clone(), ctrl_z.clone(), alt_f4];
+
+        let containing = inputs_containing(
+            InputKind::Keyboard(KeyCode::LControl),
+            inputs.clone().into_iter(),
+        );
+        assert_eq!(containing, vec![ctrl_s, ctrl_z]);
+
+        let containing = inputs_containing(InputKind::Keyboard(KeyCode::Q), inputs.into_iter());
+        assert!(containing.is_empty());
+    }
+}
@@ -2,7 +2,12 @@ use bevy::input::gamepad::GamepadEventRaw;
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
 use leafwing_input_manager::axislike::{AxisType, DualAxisData};
+use leafwing_input_manager::gamepad_profiles::DisconnectGrace;
+use leafwing_input_manager::input_macro::{InputFrame, Macro};
+use leafwing_input_manager::input_streams::{InputStreams, Trend};
 use leafwing_input_manager::prelude::*;
+use petitset::PetitSet;
+use std::time::Duration;
 
 #[derive(Actionlike, Clone, Copy, Debug)]
 enum ButtonlikeTestAction {
@@ -74,6 +79,11 @@ fn game_pad_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
 
     app.send_input(input);
@@ -93,13 +103,24 @@ fn game_pad_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
         y: SingleAxis {
             axis_type: AxisType::Gamepad(GamepadAxisType::LeftStickY),
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
+        square_output: false,
     };
     app.send_input(input);
     let mut events = app.world.resource_mut::<Events<GamepadEventRaw>>();
@@ -127,6 +148,11 @@ fn game_pad_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -139,6 +165,11 @@ fn game_pad_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -151,6 +182,11 @@ fn game_pad_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -163,6 +199,11 @@ fn game_pad_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -176,6 +217,11 @@ fn game_pad_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -188,6 +234,11 @@ fn game_pad_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -222,6 +273,193 @@ fn game_pad_dual_axis() {
     );
 }
 
+#[test]
+fn game_pad_dual_axis_square_output() {
+    let diagonal = DualAxisData::new(0.707, 0.707).circle_to_square();
+    assert!((diagonal.x() - 1.0).abs() < 0.01);
+    assert!((diagonal.y() - 1.0).abs() < 0.01);
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        DualAxis::left_stick().with_square_output(),
+        AxislikeTestAction::XY,
+    )]));
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.707,
+        0.707,
+    ));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    let axis_pair = action_state.axis_pair(AxislikeTestAction::XY).unwrap();
+    assert!((axis_pair.x() - 1.0).abs() < 0.01);
+    assert!((axis_pair.y() - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn game_pad_movement_intensity() {
+    let mut app = test_app();
+    let stick: UserInput = DualAxis::left_stick().into();
+    let keys: UserInput = VirtualDPad::wasd().into();
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.5,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.movement_intensity([&stick, &keys].into_iter()),
+        0.5
+    );
+
+    // The combined movement is capped
+    app.send_input(KeyCode::D);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.movement_intensity([&stick, &keys].into_iter()),
+        1.0
+    );
+}
+
+#[test]
+fn game_pad_input_percent() {
+    let mut app = test_app();
+    let input: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.756));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_percent(&input), 76);
+}
+
+#[test]
+fn game_pad_stick_heading_degrees() {
+    let mut app = test_app();
+    let input: UserInput = DualAxis::left_stick().into();
+
+    // Within the deadzone, there is no heading
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.01,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.stick_heading_degrees(&input), None);
+
+    // Pushing right is a quarter turn clockwise from up
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.8,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let heading = input_streams.stick_heading_degrees(&input).unwrap();
+    assert!((heading - 90.0).abs() < 0.01);
+}
+
+#[test]
+fn game_pad_disconnect_grace() {
+    const GRACE: Duration = Duration::from_millis(200);
+
+    let mut app = test_app();
+    app.insert_resource(DisconnectGrace(GRACE));
+    app.insert_resource(InputMap::new([(
+        SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1),
+        AxislikeTestAction::X,
+    )]));
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.8));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::X));
+    assert_eq!(action_state.value(AxislikeTestAction::X), 0.8);
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Disconnected,
+    });
+    app.update();
+
+    // The last-known state persists during the grace period
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::X));
+    assert_eq!(action_state.value(AxislikeTestAction::X), 0.8);
+
+    std::thread::sleep(GRACE + GRACE / 4);
+    app.update();
+
+    // Once the grace period has passed, the input snaps to zero
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(AxislikeTestAction::X));
+    assert_eq!(action_state.value(AxislikeTestAction::X), 0.0);
+}
+
+#[test]
+fn game_pad_matches_recorded_step() {
+    let mut app = test_app();
+    let input: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.5));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let mut recorded = Macro::default();
+    recorded.push(InputFrame::capture(&input_streams, [input.clone()]));
+    assert_eq!(recorded.len(), 1);
+
+    // Playing back a slightly different position is still within tolerance
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.52));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.matches_step(&recorded.steps[0], 0.05));
+    assert!(!input_streams.matches_step(&recorded.steps[0], 0.01));
+
+    // Moving the stick further away no longer matches
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.8));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.matches_step(&recorded.steps[0], 0.05));
+}
+
+#[test]
+fn game_pad_snapshot_keeps_registered_gamepads() {
+    use leafwing_input_manager::input_snapshot::{input_pressed_in, OwnedInputSnapshot};
+
+    let mut app = test_app();
+    let input: UserInput = GamepadButtonType::South.into();
+
+    app.send_input(GamepadButtonType::South);
+    app.update();
+
+    let snapshot = OwnedInputSnapshot::capture(&InputStreams::from_world(&app.world, None));
+    assert_eq!(
+        snapshot.input_streams().ordered_gamepads(),
+        vec![Gamepad { id: 1 }]
+    );
+    // The gamepad is still connected in the snapshot, so its buttons can be read
+    assert!(input_pressed_in(&snapshot, &input));
+}
+
 #[test]
 fn game_pad_virtualdpad() {
     let mut app = test_app();
@@ -244,3 +482,814 @@ fn game_pad_virtualdpad() {
         DualAxisData::new(-1.0, 0.0)
     );
 }
+
+#[test]
+fn game_pad_axis_pair_angle() {
+    let mut app = test_app();
+
+    let left_stick = DualAxis::left_stick();
+    let right_stick = DualAxis::right_stick();
+
+    // Neither stick has been moved, so there is no angle between them
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.axis_pair_angle(&left_stick.into(), &right_stick.into()),
+        None
+    );
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        1.0,
+        0.0,
+    ));
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+        0.0,
+        -1.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let angle = input_streams
+        .axis_pair_angle(&left_stick.into(), &right_stick.into())
+        .unwrap();
+    assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+}
+
+#[test]
+fn game_pad_combined_axis_pair() {
+    let mut app = test_app();
+
+    let inputs: [UserInput; 3] = [
+        VirtualDPad::wasd().into(),
+        DualAxis::left_stick().into(),
+        // Inputs without an axis pair are ignored
+        KeyCode::Space.into(),
+    ];
+
+    // Nothing is held, so there is no direction
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.combined_axis_pair(inputs.iter()),
+        DualAxisData::new(0.0, 0.0)
+    );
+
+    // Up on the keyboard and right on the stick
+    app.send_input(KeyCode::W);
+    app.send_input(KeyCode::Space);
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        1.0,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let combined = input_streams.combined_axis_pair(inputs.iter());
+    let expected = Vec2::new(1.0, 1.0).normalize();
+    assert!((combined.xy() - expected).length() < 1e-4, "{combined:?}");
+}
+
+#[test]
+fn game_pad_analog_bumper() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        GamepadButtonType::LeftTrigger,
+        ButtonlikeTestAction::Up,
+    )]));
+
+    // Bumpers on some controllers report how hard they are pressed
+    let mut events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::ButtonChanged(GamepadButtonType::LeftTrigger, 0.8),
+    });
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Up), 0.8);
+}
+
+#[test]
+fn game_pad_weakest_active_input() {
+    let mut app = test_app();
+
+    let left_x: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+    let right_x: UserInput = SingleAxis::symmetric(GamepadAxisType::RightStickX, 0.0).into();
+    let left_z: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftZ, 0.0).into();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.3));
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightStickX, 0.7));
+    app.update();
+
+    // The left z axis was never moved, so it is skipped
+    let inputs = PetitSet::<UserInput, 16>::from_iter([left_x.clone(), right_x, left_z]);
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.weakest_active_input(&inputs), Some(&left_x));
+}
+
+#[test]
+fn game_pad_single_axis_anti_deadzone() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1).with_anti_deadzone(0.3),
+        AxislikeTestAction::X,
+    )]));
+
+    // Just past the dead zone, the value jumps to the anti-deadzone floor
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.11));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::X));
+    assert_eq!(action_state.value(AxislikeTestAction::X), -0.3);
+
+    // Larger values are unaffected
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.8));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(action_state.value(AxislikeTestAction::X), 0.8);
+}
+
+#[test]
+fn game_pad_single_axis_zones() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).with_zones(0.1, 0.9),
+        AxislikeTestAction::X,
+    )]));
+
+    // Values inside the inner zone are ignored
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.08));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(AxislikeTestAction::X));
+
+    // Values between the zones are rescaled
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    let value = action_state.value(AxislikeTestAction::X);
+    assert!((value - 0.5).abs() < 1e-4, "value was {value}");
+
+    // Values beyond the outer zone are clamped
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.93));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(action_state.value(AxislikeTestAction::X), -1.0);
+}
+
+#[test]
+fn game_pad_single_axis_deadzone_curves() {
+    let mut app = test_app();
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.7));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let axis = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.2);
+    let value_with_curve = |deadzone_curve| {
+        input_streams.input_value(&axis.with_deadzone_curve(deadzone_curve).into())
+    };
+
+    let hard = value_with_curve(DeadzoneCurve::Hard);
+    let linear = value_with_curve(DeadzoneCurve::Linear);
+    let smoothstep = value_with_curve(DeadzoneCurve::Smoothstep);
+
+    assert!((hard - 0.7).abs() < 1e-4, "hard was {hard}");
+    assert!((linear - 0.625).abs() < 1e-4, "linear was {linear}");
+    assert!(
+        linear < smoothstep && smoothstep < hard,
+        "smoothstep was {smoothstep}"
+    );
+}
+
+#[test]
+fn game_pad_dual_axis_zones() {
+    let mut app = test_app();
+    let mut dual_axis = DualAxis::left_stick();
+    dual_axis.x = dual_axis.x.with_zones(0.1, 0.9);
+    dual_axis.y = dual_axis.y.with_zones(0.1, 0.9);
+    let input: UserInput = dual_axis.into();
+
+    // Values inside the inner zone are ignored
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.08,
+        0.0,
+    ));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_axis_pair(&input).unwrap().x(), 0.0);
+
+    // Values just past the inner zone are rescaled, rather than cut off by the dead zone a second time
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.15,
+        0.0,
+    ));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let x = input_streams.input_axis_pair(&input).unwrap().x();
+    assert!((x - 0.0625).abs() < 1e-4, "x was {x}");
+}
+
+#[test]
+fn game_pad_dual_axis_smoothstep() {
+    let mut app = test_app();
+    let mut dual_axis = DualAxis::symmetric(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.2,
+    );
+    dual_axis.x = dual_axis.x.with_deadzone_curve(DeadzoneCurve::Smoothstep);
+    dual_axis.y = dual_axis.y.with_deadzone_curve(DeadzoneCurve::Smoothstep);
+    let input: UserInput = dual_axis.into();
+
+    // Smoothstep eases out of the dead zone, so this is well below the dead zone threshold, but still valid
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.3,
+        0.0,
+    ));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let x = input_streams.input_axis_pair(&input).unwrap().x();
+    assert!((x - 0.04296875).abs() < 1e-4, "x was {x}");
+}
+
+#[test]
+fn game_pad_single_axis_trend() {
+    let mut app = test_app();
+    let input: UserInput = SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1).into();
+    app.insert_resource(InputMap::new([(input.clone(), AxislikeTestAction::X)]));
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.3));
+    app.update();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.6));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_trend(&input), Trend::Rising);
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_trend(&input), Trend::Steady);
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.2));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_trend(&input), Trend::Falling);
+}
+
+#[test]
+fn game_pad_first_gamepad_press() {
+    let mut app = test_app();
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 2 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.first_gamepad_press(), None);
+
+    // Only the second gamepad presses a button
+    app.send_input_as_gamepad(GamepadButtonType::South, Some(Gamepad { id: 2 }));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.first_gamepad_press(),
+        Some((Gamepad { id: 2 }, GamepadButtonType::South))
+    );
+}
+
+#[test]
+fn game_pad_multiple_associated_gamepads() {
+    let mut app = test_app();
+
+    // Register a second gamepad, to share control of the same player
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 2 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    app.send_input_as_gamepad(GamepadButtonType::South, Some(Gamepad { id: 2 }));
+    app.send_input_as_gamepad(
+        SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.3),
+        Some(Gamepad { id: 1 }),
+    );
+    app.send_input_as_gamepad(
+        SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.7),
+        Some(Gamepad { id: 2 }),
+    );
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, Some(Gamepad { id: 1 }));
+    let left_stick_x: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1).into();
+
+    // Only the associated gamepad is read
+    assert!(!input_streams.input_pressed(&GamepadButtonType::South.into()));
+    assert_eq!(input_streams.input_value(&left_stick_x), 0.3);
+
+    // Any of the associated gamepads can press buttons, and the strongest axis value wins
+    input_streams.associated_gamepads = PetitSet::from_iter([Gamepad { id: 1 }, Gamepad { id: 2 }]);
+    assert!(input_streams.input_pressed(&GamepadButtonType::South.into()));
+    assert_eq!(input_streams.input_value(&left_stick_x), -0.7);
+}
+
+#[test]
+fn game_pad_input_map_associated_gamepads() {
+    let mut app = test_app();
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 2 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    let mut input_map = InputMap::new([(GamepadButtonType::South, ButtonlikeTestAction::Up)]);
+    input_map
+        .add_gamepad(Gamepad { id: 1 })
+        .add_gamepad(Gamepad { id: 2 });
+    app.insert_resource(input_map);
+
+    // The second gamepad is read, even though it does not have the lowest id
+    app.send_input_as_gamepad(GamepadButtonType::South, Some(Gamepad { id: 2 }));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+}
+
+#[test]
+fn game_pad_axis_profiles() {
+    use leafwing_input_manager::gamepad_profiles::{AxisProfile, GamepadProfiles};
+
+    let mut app = test_app();
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 2 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    let mut gamepad_profiles = app.world.resource_mut::<GamepadProfiles>();
+    gamepad_profiles.insert(
+        Gamepad { id: 1 },
+        AxisProfile {
+            sensitivity: 2.0,
+            deadzone: 0.1,
+        },
+    );
+    gamepad_profiles.insert(
+        Gamepad { id: 2 },
+        AxisProfile {
+            sensitivity: 1.0,
+            deadzone: 0.5,
+        },
+    );
+
+    // Both gamepads report the same raw value
+    for id in [1, 2] {
+        app.send_input_as_gamepad(
+            SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.3),
+            Some(Gamepad { id }),
+        );
+    }
+    app.update();
+
+    let left_stick_x: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+
+    let input_streams = InputStreams::from_world(&app.world, Some(Gamepad { id: 1 }));
+    assert_eq!(input_streams.input_value(&left_stick_x), 0.6);
+
+    // The raw value is within the deadzone of the second gamepad
+    let input_streams = InputStreams::from_world(&app.world, Some(Gamepad { id: 2 }));
+    assert_eq!(input_streams.input_value(&left_stick_x), 0.0);
+}
+
+#[test]
+fn game_pad_calibrated_center() {
+    use leafwing_input_manager::input_streams::MutableInputStreams;
+
+    let mut app = test_app();
+
+    // The stick drifts away from zero while at rest
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.2));
+    app.update();
+
+    let left_stick_x: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_ne!(input_streams.input_value(&left_stick_x), 0.0);
+
+    let mut mutable_input_streams = MutableInputStreams::from_world(&mut app.world, None);
+    mutable_input_streams.calibrate_gamepad_center();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_value(&left_stick_x), 0.0);
+}
+
+#[test]
+fn game_pad_button_remap() {
+    use leafwing_input_manager::gamepad_profiles::GamepadButtonRemap;
+
+    let mut app = test_app();
+    app.world
+        .resource_mut::<GamepadButtonRemap>()
+        .swap(GamepadButtonType::South, GamepadButtonType::East);
+
+    app.send_input(GamepadButtonType::East);
+    app.update();
+
+    // Bindings to South now read the physical East button, and vice versa
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&GamepadButtonType::South.into()));
+    assert!(!input_streams.input_pressed(&GamepadButtonType::East.into()));
+}
+
+#[test]
+fn game_pad_input_exceeds() {
+    let mut app = test_app();
+    let input: UserInput = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.6));
+    app.update();
+
+    // The sign of the value is ignored
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_exceeds(&input, 0.5));
+    assert!(!input_streams.input_exceeds(&input, 0.7));
+}
+
+#[test]
+fn game_pad_guide_button_can_be_allowed() {
+    let mut app = test_app();
+    let input: UserInput = GamepadButtonType::Mode.into();
+
+    app.send_input(GamepadButtonType::Mode);
+    app.update();
+
+    // The guide button is reserved for the operating system by default
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&input));
+    assert_eq!(input_streams.input_value(&input), 0.0);
+
+    input_streams.allow_guide_button = true;
+    assert!(input_streams.input_pressed(&input));
+}
+
+#[test]
+fn game_pad_guide_button_allowed_by_input_settings() {
+    use leafwing_input_manager::input_streams::InputSettings;
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        GamepadButtonType::Mode,
+        ButtonlikeTestAction::Up,
+    )]));
+
+    app.send_input(GamepadButtonType::Mode);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(ButtonlikeTestAction::Up));
+
+    app.insert_resource(InputSettings {
+        allow_guide_button: true,
+        ..Default::default()
+    });
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+}
+
+#[test]
+fn game_pad_button_pressed_other_gamepads() {
+    let mut app = test_app();
+    let player_one = Gamepad { id: 1 };
+    let player_two = Gamepad { id: 2 };
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: player_two,
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    // Presses by the associated gamepad are ignored
+    app.send_input_as_gamepad(GamepadButtonType::South, Some(player_one));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, Some(player_one));
+    assert!(!input_streams.button_pressed_other_gamepads(GamepadButtonType::South));
+
+    app.send_input_as_gamepad(GamepadButtonType::South, Some(player_two));
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, Some(player_one));
+    assert!(input_streams.button_pressed_other_gamepads(GamepadButtonType::South));
+}
+
+#[test]
+fn game_pad_ordered_gamepads() {
+    let mut app = test_app();
+
+    for id in [7, 3, 5, 2] {
+        let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+        gamepad_events.send(GamepadEventRaw {
+            gamepad: Gamepad { id },
+            event_type: GamepadEventType::Connected,
+        });
+    }
+    app.update();
+    app.update();
+
+    let expected: Vec<Gamepad> = [1, 2, 3, 5, 7]
+        .into_iter()
+        .map(|id| Gamepad { id })
+        .collect();
+
+    for _ in 0..3 {
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert_eq!(input_streams.ordered_gamepads(), expected);
+        assert_eq!(input_streams.guess_gamepad(), Some(Gamepad { id: 1 }));
+        app.update();
+    }
+}
+
+#[test]
+fn game_pad_single_axis_quantize() {
+    let mut app = test_app();
+    let axis = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).with_quantize(4);
+
+    // With 4 levels, magnitudes are snapped to multiples of a third
+    for (raw_value, quantized_value) in [(0.3, 1.0 / 3.0), (0.4, 1.0 / 3.0), (-0.9, -1.0)] {
+        app.send_input(SingleAxis::from_value(
+            GamepadAxisType::LeftStickX,
+            raw_value,
+        ));
+        app.update();
+
+        let input_streams = InputStreams::from_world(&app.world, None);
+        let value = input_streams.input_value(&axis.into());
+        assert!(
+            (value - quantized_value).abs() < 1e-4,
+            "{raw_value} was quantized to {value}"
+        );
+    }
+}
+
+#[test]
+fn game_pad_virtual_dpad_digital_analog_mode() {
+    use leafwing_input_manager::axislike::VirtualDPad;
+    use leafwing_input_manager::input_streams::DigitalAnalogMode;
+    use leafwing_input_manager::user_input::InputKind;
+
+    let mut app = test_app();
+    app.send_input(KeyCode::D);
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.3));
+    app.update();
+
+    // The key pushes right at full strength, while the stick pushes left a little
+    let input: UserInput = VirtualDPad {
+        up: KeyCode::W.into(),
+        down: KeyCode::S.into(),
+        left: InputKind::SingleAxis(SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1)),
+        right: KeyCode::D.into(),
+        ramp: None,
+    }
+    .into();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    let summed = input_streams.input_value(&input);
+    assert!((summed - 0.7).abs() < 1e-4, "summed was {summed}");
+
+    input_streams.digital_analog_mode = DigitalAnalogMode::MaxMagnitude;
+    assert_eq!(input_streams.input_value(&input), 1.0);
+}
+
+#[test]
+fn game_pad_stick_directions() {
+    let mut app = test_app();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.left_stick_direction(), None);
+    assert_eq!(input_streams.right_stick_direction(), None);
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.0,
+        0.5,
+    ));
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+        -0.3,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.left_stick_direction(), Some(Vec2::Y));
+    assert_eq!(input_streams.right_stick_direction(), Some(-Vec2::X));
+
+    // Movements within the default dead zone are ignored
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.05,
+        0.05,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.left_stick_direction(), None);
+}
+
+#[test]
+fn game_pad_single_axis_absolute() {
+    let mut app = test_app();
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, -0.7));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let axis = SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1);
+
+    assert_eq!(input_streams.input_value(&axis.into()), -0.7);
+    assert_eq!(
+        input_streams.input_value(&axis.with_absolute(true).into()),
+        0.7
+    );
+}
+
+#[test]
+fn game_pad_stick_opposition() {
+    let mut app = test_app();
+    let input: UserInput = StickOpposition { threshold: 0.5 }.into();
+
+    // Sticks pushed apart
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        -0.9,
+        0.0,
+    ));
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+        0.9,
+        0.1,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&input));
+
+    // Sticks pushed the same way
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.9,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&input));
+}
+
+#[test]
+fn game_pad_input_snapped() {
+    let mut app = test_app();
+    let input: UserInput = DualAxis::left_stick().into();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_snapped(&input, 8), None);
+
+    // A mostly-rightward diagonal
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.8,
+        0.6,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+
+    let four_way = input_streams.input_snapped(&input, 4).unwrap();
+    assert!((four_way - Vec2::X).length() < 1e-4, "{four_way:?}");
+
+    let eight_way = input_streams.input_snapped(&input, 8).unwrap();
+    let diagonal = Vec2::new(1.0, 1.0).normalize();
+    assert!((eight_way - diagonal).length() < 1e-4, "{eight_way:?}");
+}
+
+#[test]
+fn game_pad_flick_stick_angle() {
+    let mut app = test_app();
+    let input: UserInput = DualAxis::left_stick().into();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.flick_stick_angle(&input), None);
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.5,
+        0.5,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let angle = input_streams.flick_stick_angle(&input).unwrap();
+    assert!(
+        (angle - std::f32::consts::FRAC_PI_4).abs() < 1e-4,
+        "{angle}"
+    );
+}
+
+#[test]
+fn game_pad_stick_dpad_is_analog() {
+    let mut app = test_app();
+    let input: UserInput = VirtualDPad::stick_dpad(DualAxis::left_stick()).into();
+
+    // Half tilted to the right
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.5,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&input));
+    assert_eq!(
+        input_streams.input_axis_pair(&input),
+        Some(DualAxisData::new(0.5, 0.0))
+    );
+
+    // Half tilted down
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.0,
+        -0.5,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.input_axis_pair(&input),
+        Some(DualAxisData::new(0.0, -0.5))
+    );
+}
+
+#[test]
+fn game_pad_stick_dpad_presses_tilted_direction_only() {
+    let mut app = test_app();
+    let dpad = VirtualDPad::stick_dpad(DualAxis::left_stick());
+
+    // Tilted to the right
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.8,
+        0.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.button_pressed(dpad.right));
+    assert!(!input_streams.button_pressed(dpad.left));
+    assert!(!input_streams.button_pressed(dpad.up));
+    assert!(!input_streams.button_pressed(dpad.down));
+    assert_eq!(
+        input_streams.input_axis_pair(&dpad.into()),
+        Some(DualAxisData::new(0.8, 0.0))
+    );
+}
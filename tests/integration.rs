@@ -327,3 +327,425 @@ fn duration() {
         .resource::<ActionState<Action>>()
         .pressed(Action::PayRespects));
 }
+
+#[test]
+fn input_streams_has_events() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::InputStreams;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    // Idle frame
+    app.update();
+    assert!(!InputStreams::from_world(&app.world, None).has_events());
+
+    // Press
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(InputStreams::from_world(&app.world, None).has_events());
+
+    // Hold
+    app.update();
+    assert!(!InputStreams::from_world(&app.world, None).has_events());
+}
+
+#[test]
+fn input_streams_activity_score() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::axislike::MouseMotionAxisType;
+    use leafwing_input_manager::input_streams::InputStreams;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    // Idle frame
+    app.update();
+    assert_eq!(
+        InputStreams::from_world(&app.world, None).input_activity_score(),
+        0.0
+    );
+
+    // A single key press
+    app.send_input(KeyCode::F);
+    app.update();
+    let single_press = InputStreams::from_world(&app.world, None).input_activity_score();
+    assert_eq!(single_press, 1.0);
+
+    // Several presses and some mouse movement
+    app.send_input(KeyCode::G);
+    app.send_input(MouseButton::Left);
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+    let busy_frame = InputStreams::from_world(&app.world, None).input_activity_score();
+    assert!(busy_frame > single_press, "score was {busy_frame}");
+}
+
+#[test]
+fn input_streams_active_modifiers() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::InputStreams;
+    use leafwing_input_manager::user_input::Modifier;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    app.send_input(KeyCode::LControl);
+    app.send_input(KeyCode::RShift);
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let active_modifiers = input_streams.active_modifiers();
+    assert_eq!(active_modifiers.len(), 2);
+    assert!(active_modifiers.contains(&Modifier::Control));
+    assert!(active_modifiers.contains(&Modifier::Shift));
+}
+
+#[test]
+fn input_streams_category_changed() {
+    use bevy::input::gamepad::GamepadEventRaw;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::InputStreams;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.category_changed(InputCategory::Gamepad));
+
+    app.send_input(GamepadButtonType::South);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.category_changed(InputCategory::Gamepad));
+    assert!(!input_streams.category_changed(InputCategory::Keyboard));
+    assert!(!input_streams.category_changed(InputCategory::Mouse));
+
+    // Holding the button is not a change
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.category_changed(InputCategory::Gamepad));
+}
+
+#[test]
+fn input_alias_resolves_to_chord() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::errors::CyclicAlias;
+    use leafwing_input_manager::input_alias::{AliasId, InputAlias};
+
+    let mut input_aliases = InputAlias::default();
+    input_aliases
+        .insert(
+            AliasId(0),
+            UserInput::chord([KeyCode::LControl, KeyCode::F]),
+        )
+        .unwrap();
+    // Aliases may refer to other aliases...
+    input_aliases
+        .insert(AliasId(1), UserInput::Alias(AliasId(0)))
+        .unwrap();
+    // ...but never to themselves
+    assert_eq!(
+        input_aliases.insert(AliasId(0), UserInput::Alias(AliasId(1))),
+        Err(CyclicAlias)
+    );
+    // ...including through the inputs nested inside of a preferred input
+    assert_eq!(
+        input_aliases.insert(
+            AliasId(2),
+            UserInput::Preferred(vec![UserInput::Alias(AliasId(2))])
+        ),
+        Err(CyclicAlias)
+    );
+    assert_eq!(
+        input_aliases.insert(
+            AliasId(0),
+            UserInput::Preferred(vec![KeyCode::F.into(), UserInput::Alias(AliasId(1))])
+        ),
+        Err(CyclicAlias)
+    );
+    assert_eq!(input_aliases.get(AliasId(2)), None);
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(input_aliases)
+        .insert_resource(InputMap::<Action>::new([(
+            UserInput::Alias(AliasId(1)),
+            Action::PayRespects,
+        )]));
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .released(Action::PayRespects));
+
+    app.send_input(KeyCode::LControl);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::PayRespects));
+}
+
+#[test]
+fn custom_input_reads_input_streams() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::user_input::CustomInput;
+
+    let both_keys = CustomInput::new(|input_streams| {
+        if input_streams.keycode.pressed(KeyCode::F) && input_streams.keycode.pressed(KeyCode::G) {
+            1.0
+        } else {
+            0.0
+        }
+    });
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(both_keys, Action::PayRespects)]));
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .released(Action::PayRespects));
+
+    app.send_input(KeyCode::G);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::PayRespects));
+}
+
+#[test]
+fn input_pressed_filtered_by_kind() {
+    use bevy::input::gamepad::GamepadEventRaw;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::InputStreams;
+    use leafwing_input_manager::user_input::InputKind;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    app.send_input(KeyCode::LShift);
+    app.send_input(GamepadButtonType::South);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let mixed_chord = UserInput::chord([
+        InputKind::Keyboard(KeyCode::LShift),
+        InputKind::GamepadButton(GamepadButtonType::South),
+    ]);
+    let gamepad_chord = UserInput::chord([GamepadButtonType::South, GamepadButtonType::East]);
+
+    assert!(input_streams.input_pressed_filtered(&mixed_chord, InputKindMask::ALL));
+    assert!(input_streams.input_pressed_filtered(
+        &mixed_chord,
+        InputKindMask::KEYBOARD | InputKindMask::GAMEPAD
+    ));
+    // The keyboard half of the chord is ignored, so the chord cannot be pressed
+    assert!(!input_streams.input_pressed_filtered(&mixed_chord, InputKindMask::GAMEPAD));
+    assert!(input_streams
+        .input_pressed_filtered(&GamepadButtonType::South.into(), InputKindMask::GAMEPAD));
+    assert!(!input_streams.input_pressed_filtered(&gamepad_chord, InputKindMask::GAMEPAD));
+}
+
+#[test]
+fn input_streams_shared_inputs() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::InputStreams;
+    use leafwing_input_manager::user_input::InputKind;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    app.send_input(KeyCode::F);
+    app.send_input(KeyCode::G);
+    app.update();
+
+    let pay_respects: UserInput = KeyCode::F.into();
+    let shift_pay_respects = UserInput::chord([KeyCode::LShift, KeyCode::F]);
+    let unrelated: UserInput = KeyCode::G.into();
+    let inputs = [pay_respects.clone(), shift_pay_respects.clone(), unrelated];
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let shared_inputs = input_streams.shared_inputs(inputs.iter());
+
+    assert_eq!(
+        shared_inputs,
+        vec![(
+            InputKind::Keyboard(KeyCode::F),
+            vec![&pay_respects, &shift_pay_respects]
+        )]
+    );
+}
+
+#[test]
+fn input_match_hooks_fire_when_pressed() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_hooks::InputMatchHooks;
+    use leafwing_input_manager::input_streams::InputStreams;
+    use std::sync::{Arc, Mutex};
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    let matches = Arc::new(Mutex::new(Vec::new()));
+    let recorded_matches = matches.clone();
+    app.world.resource_mut::<InputMatchHooks>().insert(
+        KeyCode::F,
+        move |input: &UserInput, value: f32| {
+            recorded_matches
+                .lock()
+                .unwrap()
+                .push((input.clone(), value));
+        },
+    );
+
+    let pay_respects: UserInput = KeyCode::F.into();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&pay_respects));
+    assert!(matches.lock().unwrap().is_empty());
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&pay_respects));
+    assert_eq!(*matches.lock().unwrap(), vec![(pay_respects, 1.0)]);
+}
+
+#[test]
+fn input_match_hooks_fire_once_per_frame() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_hooks::InputMatchHooks;
+    use std::sync::{Arc, Mutex};
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]));
+
+    let match_count = Arc::new(Mutex::new(0));
+    let recorded_match_count = match_count.clone();
+    app.world.resource_mut::<InputMatchHooks>().insert(
+        KeyCode::F,
+        move |_input: &UserInput, _value: f32| {
+            *recorded_match_count.lock().unwrap() += 1;
+        },
+    );
+
+    // Pressing the action, resolving clashes and recording the input history only runs the hook once
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(*match_count.lock().unwrap(), 1);
+
+    app.update();
+    assert_eq!(*match_count.lock().unwrap(), 2);
+}
+
+#[test]
+fn input_streams_remote_flag() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_streams::{InputStreams, RemoteInput};
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.is_remote());
+
+    input_streams.remote_input = RemoteInput(true);
+    assert!(input_streams.is_remote());
+}
+
+#[test]
+fn input_pressed_in_context() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_context::{ActiveContexts, InputContext};
+    use leafwing_input_manager::input_streams::InputStreams;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    let on_foot = InputContext::new("on_foot");
+    let in_vehicle = InputContext::new("in_vehicle");
+    app.world
+        .resource_mut::<ActiveContexts>()
+        .activate(on_foot.clone());
+
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let input: UserInput = KeyCode::F.into();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed_in_context(&input, &on_foot));
+    // The vehicle controls are inactive while on foot
+    assert!(!input_streams.input_pressed_in_context(&input, &in_vehicle));
+}
@@ -0,0 +1,492 @@
+use bevy::input::gamepad::GamepadEventRaw;
+use bevy::input::keyboard::{KeyboardInput, ScanCode};
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::prelude::*;
+use leafwing_input_manager::input_streams::InputStreams;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Undo,
+    Confirm,
+}
+
+/// The scan code of the key labelled `Z` on a QWERTY keyboard, which is labelled `W` on an AZERTY keyboard
+const QWERTY_Z: ScanCode = ScanCode(44);
+/// The scan code of the key labelled `W` on a QWERTY keyboard, which is labelled `Z` on an AZERTY keyboard
+const QWERTY_W: ScanCode = ScanCode(17);
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default());
+
+    app
+}
+
+/// Presses a key on a simulated AZERTY keyboard, where the key code does not match the QWERTY scan code
+fn press_azerty_key(app: &mut App, scan_code: ScanCode, key_code: KeyCode) {
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(KeyboardInput {
+        scan_code: scan_code.0,
+        key_code: Some(key_code),
+        state: ButtonState::Pressed,
+    });
+}
+
+fn undo_pressed(app: &App, key_resolution: KeyResolution) -> bool {
+    let mut input_map = InputMap::new([(KeyCode::Z, Action::Undo)]);
+    input_map.insert(QWERTY_Z, Action::Undo);
+    input_map.set_key_resolution(key_resolution);
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    input_map.pressed(Action::Undo, &input_streams, ClashStrategy::PressAll)
+}
+
+#[test]
+fn key_location_is_layout_independent() {
+    let mut app = test_app();
+
+    app.send_input(QWERTY_Z);
+    app.update();
+    assert!(app.pressed(QWERTY_Z));
+    assert!(!app.pressed(KeyCode::Z));
+
+    app.release_input(QWERTY_Z);
+    app.update();
+    assert!(!app.pressed(QWERTY_Z));
+}
+
+#[test]
+fn key_resolution_policies_diverge() {
+    let mut app = test_app();
+
+    // The key in the QWERTY `Z` position produces a `W`
+    press_azerty_key(&mut app, QWERTY_Z, KeyCode::W);
+    app.update();
+
+    assert!(!undo_pressed(&app, KeyResolution::PreferKeyCode));
+    assert!(undo_pressed(&app, KeyResolution::PreferScanCode));
+    assert!(undo_pressed(&app, KeyResolution::Either));
+
+    // The key in the QWERTY `W` position produces a `Z`
+    app.reset_inputs();
+    press_azerty_key(&mut app, QWERTY_W, KeyCode::Z);
+    app.update();
+
+    assert!(undo_pressed(&app, KeyResolution::PreferKeyCode));
+    assert!(!undo_pressed(&app, KeyResolution::PreferScanCode));
+    assert!(undo_pressed(&app, KeyResolution::Either));
+}
+
+#[test]
+fn press_kind_across_frames() {
+    use leafwing_input_manager::input_streams::PressKind;
+    use leafwing_input_manager::user_input::InputKind;
+
+    const INPUT: InputKind = InputKind::Keyboard(KeyCode::Q);
+
+    let mut app = test_app();
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::None);
+
+    app.send_input(KeyCode::Q);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::JustPressed);
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::Held);
+
+    app.release_input(KeyCode::Q);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::Released);
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::None);
+}
+
+#[test]
+fn press_kind_respects_text_input() {
+    use leafwing_input_manager::input_streams::PressKind;
+    use leafwing_input_manager::user_input::InputKind;
+
+    const INPUT: InputKind = InputKind::Keyboard(KeyCode::Q);
+
+    let mut app = test_app();
+    app.insert_resource(TextInputActive(true));
+    app.send_input(KeyCode::Q);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.press_kind(&INPUT), PressKind::None);
+
+    // Edge-triggered inputs must not fire while the text field has focus
+    let hold_tap = UserInput::HoldTap {
+        hold: InputKind::Keyboard(KeyCode::LShift),
+        tap: INPUT,
+    };
+    app.send_input(KeyCode::LShift);
+    app.update();
+    app.release_input(KeyCode::Q);
+    app.update();
+    app.send_input(KeyCode::Q);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&hold_tap));
+}
+
+#[test]
+fn chord_is_exact_rejects_extra_keys() {
+    use leafwing_input_manager::user_input::InputKind;
+    use petitset::PetitSet;
+
+    let ctrl_s: PetitSet<InputKind, 8> = [KeyCode::LControl, KeyCode::S]
+        .into_iter()
+        .map(InputKind::Keyboard)
+        .collect();
+
+    let mut app = test_app();
+    app.send_input(KeyCode::LControl);
+    app.send_input(KeyCode::S);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.chord_is_exact(&ctrl_s));
+
+    // Ctrl + Shift + S contains Ctrl + S, but is not exactly it
+    app.send_input(KeyCode::LShift);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.all_buttons_pressed(&ctrl_s));
+    assert!(!input_streams.chord_is_exact(&ctrl_s));
+}
+
+#[test]
+fn text_input_suppresses_keyboard_only() {
+    let mut app = test_app();
+    app.init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::new([
+            (UserInput::from(KeyCode::Z), Action::Undo),
+            (GamepadButtonType::South.into(), Action::Confirm),
+        ]))
+        .insert_resource(TextInputActive(true));
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    app.send_input(KeyCode::Z);
+    app.send_input(GamepadButtonType::South);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Undo));
+    assert!(action_state.pressed(Action::Confirm));
+
+    // Once the text field loses focus, the key works again
+    app.insert_resource(TextInputActive(false));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Undo));
+}
+
+#[test]
+fn chord_just_pressed_needs_simultaneous_presses() {
+    use leafwing_input_manager::user_input::InputKind;
+    use petitset::PetitSet;
+
+    let ctrl_s: PetitSet<InputKind, 8> = [KeyCode::LControl, KeyCode::S]
+        .into_iter()
+        .map(InputKind::Keyboard)
+        .collect();
+
+    // Both members pressed on the same frame
+    let mut app = test_app();
+    app.send_input(KeyCode::LControl);
+    app.send_input(KeyCode::S);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.chord_just_pressed(&ctrl_s));
+
+    // Once held, the chord is no longer just pressed
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.chord_just_pressed(&ctrl_s));
+
+    // Ctrl is held over from an earlier frame
+    let mut app = test_app();
+    app.send_input(KeyCode::LControl);
+    app.update();
+    app.send_input(KeyCode::S);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.all_buttons_pressed(&ctrl_s));
+    assert!(!input_streams.chord_just_pressed(&ctrl_s));
+}
+
+#[test]
+fn external_focus_suppresses_flagged_inputs() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([
+        (KeyCode::Z, Action::Undo),
+        (KeyCode::Return, Action::Confirm),
+    ]);
+    input_map.set_respect_external_focus(KeyCode::Z, true);
+
+    app.init_resource::<ActionState<Action>>()
+        .insert_resource(input_map)
+        .insert_resource(ExternalFocus(true));
+
+    app.send_input(KeyCode::Z);
+    app.send_input(KeyCode::Return);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Undo));
+    assert!(action_state.pressed(Action::Confirm));
+
+    // Once focus returns to the game, the flagged key works again
+    app.insert_resource(ExternalFocus(false));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Undo));
+}
+
+#[test]
+fn dpad_bitmask_packs_pressed_directions() {
+    let mut app = test_app();
+    let arrow_keys = VirtualDPad::arrow_keys();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.dpad_bitmask(&arrow_keys), 0);
+
+    app.send_input(KeyCode::Up);
+    app.send_input(KeyCode::Right);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.dpad_bitmask(&arrow_keys),
+        VirtualDPad::UP_BIT | VirtualDPad::RIGHT_BIT
+    );
+    assert_eq!(input_streams.dpad_bitmask(&arrow_keys), 0b1001);
+}
+
+#[test]
+fn chord_pressed_in_snapshot() {
+    use leafwing_input_manager::input_snapshot::{input_pressed_in, OwnedInputSnapshot};
+
+    let ctrl_s = UserInput::chord([KeyCode::LControl, KeyCode::S]);
+
+    let mut app = test_app();
+    app.send_input(KeyCode::LControl);
+    app.send_input(KeyCode::S);
+    app.update();
+
+    let snapshot = OwnedInputSnapshot::capture(&InputStreams::from_world(&app.world, None));
+    assert!(input_pressed_in(&snapshot, &ctrl_s));
+
+    // The snapshot is unaffected by later input
+    app.release_input(KeyCode::S);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&ctrl_s));
+    assert!(input_pressed_in(&snapshot, &ctrl_s));
+    assert!(!input_pressed_in(&snapshot, &KeyCode::Z.into()));
+}
+
+#[test]
+fn minimal_inputs_are_sufficient() {
+    use leafwing_input_manager::user_input::InputKind;
+
+    let ctrl_s = UserInput::chord([KeyCode::LControl, KeyCode::S]);
+    let arrow_keys: UserInput = VirtualDPad::arrow_keys().into();
+
+    // Every member of a chord is needed
+    assert_eq!(
+        ctrl_s.minimal_inputs(),
+        vec![
+            InputKind::Keyboard(KeyCode::LControl),
+            InputKind::Keyboard(KeyCode::S)
+        ]
+    );
+    // A single direction is enough to press a dpad
+    assert_eq!(
+        arrow_keys.minimal_inputs(),
+        vec![InputKind::Keyboard(KeyCode::Up)]
+    );
+
+    let mut app = test_app();
+    for input_kind in ctrl_s
+        .minimal_inputs()
+        .into_iter()
+        .chain(arrow_keys.minimal_inputs())
+    {
+        app.send_input(input_kind);
+    }
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&ctrl_s));
+    assert!(input_streams.input_pressed(&arrow_keys));
+}
+
+#[test]
+fn pressed_scan_codes_lists_physical_keys() {
+    let mut app = test_app();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.pressed_scan_codes().is_empty());
+
+    press_azerty_key(&mut app, QWERTY_Z, KeyCode::W);
+    press_azerty_key(&mut app, QWERTY_W, KeyCode::Z);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.pressed_scan_codes(), vec![QWERTY_W, QWERTY_Z]);
+}
+
+#[test]
+fn keyboard_state_source_reads_unprocessed_events() {
+    use leafwing_input_manager::input_streams::KeyboardStateSource;
+
+    let mut app = test_app();
+    app.update();
+
+    // The event has been sent, but `Input<KeyCode>` is not updated until the next frame
+    press_azerty_key(&mut app, QWERTY_Z, KeyCode::W);
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_pressed(&KeyCode::W.into()));
+
+    input_streams.keyboard_state_source = KeyboardStateSource::Events;
+    assert!(input_streams.input_pressed(&KeyCode::W.into()));
+    assert!(input_streams.input_pressed(&QWERTY_Z.into()));
+    assert!(!input_streams.input_pressed(&KeyCode::Z.into()));
+}
+
+#[test]
+fn preferred_input_falls_back_to_keyboard_without_gamepad() {
+    let mut app = test_app();
+    app.send_input(KeyCode::W);
+    app.update();
+
+    let input = UserInput::Preferred(vec![
+        DualAxis::left_stick().into(),
+        VirtualDPad::wasd().into(),
+    ]);
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.input_satisfiable(&DualAxis::left_stick().into()));
+    assert!(input_streams.input_satisfiable(&input));
+    assert!(input_streams.input_pressed(&input));
+    assert_eq!(
+        input_streams.input_axis_pair(&input).unwrap().xy(),
+        Vec2::new(0.0, 1.0)
+    );
+}
+
+#[test]
+fn input_value_averaged_over_frames() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(KeyCode::Z, Action::Undo)]));
+    let input: UserInput = KeyCode::Z.into();
+
+    // The key alternates between released and pressed
+    app.update();
+    app.send_input(KeyCode::Z);
+    app.update();
+    app.release_input(KeyCode::Z);
+    app.update();
+    app.send_input(KeyCode::Z);
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_value(&input), 1.0);
+    assert_eq!(input_streams.input_value_averaged(&input, 4), 0.5);
+    assert_eq!(input_streams.input_value_averaged(&input, 2), 0.5);
+    assert_eq!(input_streams.input_value_averaged(&input, 1), 1.0);
+}
+
+#[test]
+fn analog_keyboard_pressure() {
+    use leafwing_input_manager::input_streams::AnalogKeyboard;
+
+    let mut app = test_app();
+    app.world
+        .resource_mut::<AnalogKeyboard>()
+        .set(KeyCode::W, 0.4);
+
+    app.send_input(KeyCode::W);
+    app.send_input(KeyCode::S);
+    app.update();
+
+    // Keys without a recorded pressure fall back to their binary value
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_value(&KeyCode::W.into()), 0.4);
+    assert_eq!(input_streams.input_value(&KeyCode::S.into()), 1.0);
+}
+
+#[test]
+fn keyboard_and_mouse_chord() {
+    use leafwing_input_manager::user_input::InputKind;
+
+    let mut app = test_app();
+    let ctrl_click = UserInput::chord([
+        InputKind::Keyboard(KeyCode::LControl),
+        InputKind::Mouse(MouseButton::Left),
+    ]);
+
+    app.send_input(KeyCode::LControl);
+    app.send_input(MouseButton::Left);
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&ctrl_click));
+
+    // Without a mouse, the chord cannot be pressed
+    input_streams.mouse_button = None;
+    assert!(!input_streams.input_pressed(&ctrl_click));
+    assert!(input_streams.input_pressed(&KeyCode::LControl.into()));
+}
+
+#[test]
+fn alt_f4_is_os_reserved() {
+    let app = test_app();
+    let input_streams = InputStreams::from_world(&app.world, None);
+
+    let alt_f4 = UserInput::chord([KeyCode::LAlt, KeyCode::F4]);
+    let ctrl_alt_f4 = UserInput::chord([KeyCode::LControl, KeyCode::RAlt, KeyCode::F4]);
+
+    // Alt + F4 closes the focused window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        assert!(input_streams.is_os_reserved(&alt_f4));
+        assert!(input_streams.is_os_reserved(&ctrl_alt_f4));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        assert!(!input_streams.is_os_reserved(&alt_f4));
+        assert!(!input_streams.is_os_reserved(&ctrl_alt_f4));
+    }
+
+    assert!(!input_streams.is_os_reserved(&KeyCode::F4.into()));
+}
@@ -3,6 +3,7 @@ use bevy::input::InputPlugin;
 use bevy::prelude::*;
 use leafwing_input_manager::axislike::{AxisType, DualAxisData, MouseMotionAxisType};
 use leafwing_input_manager::buttonlike::MouseMotionDirection;
+use leafwing_input_manager::input_streams::InputStreams;
 use leafwing_input_manager::prelude::*;
 
 #[derive(Actionlike, Clone, Copy, Debug)]
@@ -73,6 +74,11 @@ fn mouse_motion_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
 
     app.send_input(input);
@@ -92,13 +98,24 @@ fn mouse_motion_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
         y: SingleAxis {
             axis_type: AxisType::MouseMotion(MouseMotionAxisType::Y),
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
+        square_output: false,
     };
     app.send_input(input);
     let mut events = app.world.resource_mut::<Events<MouseMotion>>();
@@ -165,6 +182,11 @@ fn mouse_motion_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -177,6 +199,11 @@ fn mouse_motion_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -189,6 +216,11 @@ fn mouse_motion_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -201,6 +233,11 @@ fn mouse_motion_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -214,6 +251,11 @@ fn mouse_motion_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -226,6 +268,11 @@ fn mouse_motion_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -287,3 +334,346 @@ fn mouse_motion_virtualdpad() {
         DualAxisData::new(0.0, -1.0)
     );
 }
+
+#[test]
+fn mouse_motion_max_delta_per_event() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    // A sudden jump, such as when the window regains focus
+    events.send(MouseMotion {
+        delta: Vec2::new(1000.0, 0.0),
+    });
+    // Ordinary small motions
+    events.send(MouseMotion {
+        delta: Vec2::new(1.0, 2.0),
+    });
+    events.send(MouseMotion {
+        delta: Vec2::new(0.0, -1.0),
+    });
+
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.total_mouse_movement(), Vec2::new(1001.0, 1.0));
+
+    // Only the large event is capped
+    input_streams.max_delta_per_event = Some(10.0);
+    assert_eq!(input_streams.total_mouse_movement(), Vec2::new(11.0, 1.0));
+}
+
+#[test]
+fn mouse_motion_rotated() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(1.0, 0.0),
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let rotated = input_streams.mouse_motion_rotated(std::f32::consts::FRAC_PI_2);
+    assert!(rotated.abs_diff_eq(Vec2::new(0.0, 1.0), 1e-6));
+}
+
+#[test]
+fn mouse_motion_event_counts() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    for _ in 0..3 {
+        events.send(MouseMotion {
+            delta: Vec2::new(1.0, 0.0),
+        });
+    }
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let event_counts = input_streams.event_counts();
+    assert_eq!(event_counts.mouse_motion, 3);
+    assert_eq!(event_counts.mouse_wheel, 0);
+
+    // Counting does not consume the events
+    assert_eq!(input_streams.event_counts(), event_counts);
+}
+
+#[test]
+fn mouse_motion_is_reread_by_new_streams() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(1.0, 0.0),
+    });
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.total_mouse_movement(), Vec2::new(1.0, 0.0));
+
+    // New events arrive between sub-steps of the same frame
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(0.0, 2.0),
+    });
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.total_mouse_movement(), Vec2::new(1.0, 2.0));
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "debug_input_warnings"))]
+fn large_mouse_motion_warns() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(50.0, 0.0),
+    });
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let input: UserInput = SingleAxis::mouse_motion_x().into();
+    let value = input_streams.input_value(&input);
+    assert_eq!(value, 50.0);
+    assert!(input_streams.warn_if_out_of_bounds(&input, value));
+
+    // Values within the bound do not warn
+    assert!(!input_streams.warn_if_out_of_bounds(&input, 0.5));
+}
+
+#[test]
+fn mouse_motion_input_direction() {
+    let mut app = test_app();
+    let input: UserInput = DualAxis::mouse_motion().into();
+
+    // There is no direction without any motion
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.input_direction(&input), None);
+
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let direction = input_streams.input_direction(&input).unwrap();
+    assert!(
+        (direction - Vec2::new(0.6, 0.8)).length() < 1e-4,
+        "{direction:?}"
+    );
+}
+
+#[test]
+fn mouse_motion_constrained_to_axis() {
+    let mut app = test_app();
+
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.mouse_motion_constrained(MouseMotionAxisType::X),
+        3.0
+    );
+    assert_eq!(
+        input_streams.mouse_motion_constrained(MouseMotionAxisType::Y),
+        4.0
+    );
+}
+
+#[test]
+fn mouse_is_moving_threshold() {
+    let mut app = test_app();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.mouse_is_moving(0.0));
+
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.mouse_is_moving(4.0));
+    assert!(!input_streams.mouse_is_moving(5.0));
+}
+
+#[test]
+fn mouse_motion_accumulates_across_frames() {
+    use leafwing_input_manager::input_history::InputHistory;
+
+    let mut app = test_app();
+
+    // Two render frames run before the next fixed step
+    for delta in [Vec2::new(1.0, 2.0), Vec2::new(3.0, -1.0)] {
+        let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+        events.send(MouseMotion { delta });
+        app.update();
+    }
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.accumulated_input().unwrap().mouse_motion,
+        Vec2::new(4.0, 1.0)
+    );
+
+    // The fixed step consumes the accumulated motion
+    let mut input_history = app.world.resource_mut::<InputHistory>();
+    assert_eq!(
+        input_history.drain_accumulated().mouse_motion,
+        Vec2::new(4.0, 1.0)
+    );
+    assert_eq!(input_history.accumulated().mouse_motion, Vec2::ZERO);
+}
+
+#[test]
+fn mouse_sample_rate_measures_event_rate() {
+    use bevy::utils::{Duration, Instant};
+    use leafwing_input_manager::input_history::MouseSampleRate;
+
+    let mut app = test_app();
+    app.update();
+
+    // Four events arrive every 10 milliseconds, for a rate of 400 events per second
+    let start = Instant::now();
+    let mut mouse_sample_rate = MouseSampleRate::default();
+    for frame in 0..=20 {
+        mouse_sample_rate.record(start + Duration::from_millis(10 * frame), 4);
+    }
+    app.insert_resource(mouse_sample_rate);
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let rate = input_streams.mouse_sample_rate().unwrap();
+    assert!((rate - 400.0).abs() < 1e-2, "{rate}");
+
+    // Frames outside of the window are discarded
+    let mut mouse_sample_rate = MouseSampleRate::new(Duration::from_millis(50));
+    for frame in 0..=20 {
+        let events = if frame < 15 { 100 } else { 1 };
+        mouse_sample_rate.record(start + Duration::from_millis(10 * frame), events);
+    }
+    let rate = mouse_sample_rate.events_per_second();
+    assert!((rate - 100.0).abs() < 1e-2, "{rate}");
+}
+
+#[test]
+fn mouse_motion_follows_cursor_grab() {
+    use leafwing_input_manager::input_streams::MouseMotionMode;
+
+    let mut app = test_app();
+    let input: UserInput = SingleAxis::mouse_motion_x().into();
+
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    input_streams.cursor_position = Some(Vec2::new(200.0, 100.0));
+
+    // By default, the relative movement is always used
+    input_streams.cursor_grabbed = false;
+    assert_eq!(input_streams.input_value(&input), 3.0);
+
+    // While the cursor is free, the absolute cursor position is used
+    input_streams.mouse_motion_mode = MouseMotionMode::FollowCursorGrab;
+    assert_eq!(input_streams.input_value(&input), 200.0);
+
+    // While the cursor is grabbed, the relative movement is used
+    input_streams.cursor_grabbed = true;
+    assert_eq!(input_streams.input_value(&input), 3.0);
+}
+
+#[test]
+fn latest_mouse_motion_reads_last_event() {
+    let mut app = test_app();
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.latest_mouse_motion(), None);
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(5.0, 1.0),
+    });
+    events.send(MouseMotion {
+        delta: Vec2::new(-2.0, 3.0),
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.total_mouse_movement(), Vec2::new(3.0, 4.0));
+    assert_eq!(
+        input_streams.latest_mouse_motion(),
+        Some(Vec2::new(-2.0, 3.0))
+    );
+}
+
+#[test]
+fn mouse_motion_clamped_to_unit_range() {
+    let mut app = test_app();
+    app.update();
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(30.0, -40.0),
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let x: UserInput = SingleAxis::mouse_motion_x().into();
+    let y: UserInput = SingleAxis::mouse_motion_y().into();
+    let xy: UserInput = DualAxis::mouse_motion().into();
+
+    // The raw mouse motion is far outside of the unit range
+    assert!(input_streams.input_value(&x) > 1.0);
+    assert!(input_streams.input_axis_pair(&xy).unwrap().length() > 1.0);
+
+    assert_eq!(input_streams.input_value_clamped(&x), 1.0);
+    assert_eq!(input_streams.input_value_clamped(&y), -1.0);
+
+    // Clamping the pair preserves its direction
+    let clamped_xy = input_streams.input_axis_pair_clamped(&xy).unwrap();
+    assert!((clamped_xy.x() - 0.6).abs() < 0.001);
+    assert!((clamped_xy.y() + 0.8).abs() < 0.001);
+}
+
+#[test]
+fn motion_source_reports_mouse() {
+    use leafwing_input_manager::input_streams::MotionSource;
+
+    let mut app = test_app();
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.motion_source(), MotionSource::None);
+
+    let mut events = app.world.resource_mut::<Events<MouseMotion>>();
+    events.send(MouseMotion {
+        delta: Vec2::new(2.0, -1.0),
+    });
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.motion_source(), MotionSource::Mouse);
+}
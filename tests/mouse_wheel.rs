@@ -2,6 +2,9 @@ use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
 use leafwing_input_manager::axislike::{AxisType, DualAxisData, MouseWheelAxisType};
+use leafwing_input_manager::input_streams::{
+    InputStreams, ScrollSourcePriority, DEFAULT_PIXELS_PER_LINE,
+};
 use leafwing_input_manager::prelude::*;
 
 #[derive(Actionlike, Clone, Copy, Debug)]
@@ -74,6 +77,11 @@ fn mouse_wheel_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
 
     app.send_input(input);
@@ -93,13 +101,24 @@ fn mouse_wheel_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
         y: SingleAxis {
             axis_type: AxisType::MouseWheel(MouseWheelAxisType::Y),
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            anti_deadzone: 0.0,
+            outer_zone: None,
+            deadzone_curve: DeadzoneCurve::Hard,
+            quantize: None,
+            absolute: false,
         },
+        square_output: false,
     };
     app.send_input(input);
     let mut events = app.world.resource_mut::<Events<MouseWheel>>();
@@ -166,6 +185,11 @@ fn mouse_wheel_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -178,6 +202,11 @@ fn mouse_wheel_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -190,6 +219,11 @@ fn mouse_wheel_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -202,6 +236,11 @@ fn mouse_wheel_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -215,6 +254,11 @@ fn mouse_wheel_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -227,6 +271,11 @@ fn mouse_wheel_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        anti_deadzone: 0.0,
+        outer_zone: None,
+        deadzone_curve: DeadzoneCurve::Hard,
+        quantize: None,
+        absolute: false,
     };
     app.send_input(input);
     app.update();
@@ -288,3 +337,271 @@ fn mouse_wheel_virtualdpad() {
         DualAxisData::new(0.0, -1.0)
     );
 }
+
+#[test]
+fn mouse_wheel_scroll_threshold() {
+    let mut app = test_app();
+
+    // A tiny trackpad scroll
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: 0.0,
+        y: 0.5,
+    });
+
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.input_pressed(&MouseWheelDirection::Up.into()));
+
+    input_streams.scroll_threshold = 1.0;
+    assert!(!input_streams.input_pressed(&MouseWheelDirection::Up.into()));
+    assert_eq!(
+        input_streams.input_value(&SingleAxis::symmetric(MouseWheelAxisType::Y, 0.0).into()),
+        0.0
+    );
+}
+
+#[test]
+fn mouse_wheel_pixels_per_line() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 1.0,
+    });
+
+    app.update();
+
+    let mut coarse_streams = InputStreams::from_world(&app.world, None);
+    coarse_streams.pixels_per_line = Some(20.0);
+    let mut fine_streams = InputStreams::from_world(&app.world, None);
+    fine_streams.pixels_per_line = Some(5.0);
+
+    assert_eq!(
+        coarse_streams.total_mouse_wheel_movement(),
+        Vec2::new(0.0, 20.0)
+    );
+    assert_eq!(
+        fine_streams.total_mouse_wheel_movement(),
+        Vec2::new(0.0, 5.0)
+    );
+}
+
+#[test]
+fn mouse_wheel_events_are_not_consumed() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 1.0,
+    });
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: -3.0,
+        y: 0.0,
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let mouse_wheel_events = input_streams.mouse_wheel_events();
+    assert_eq!(mouse_wheel_events.len(), 2);
+    assert_eq!(mouse_wheel_events[0].unit, MouseScrollUnit::Line);
+    assert_eq!(mouse_wheel_events[0].y, 1.0);
+    assert_eq!(mouse_wheel_events[1].unit, MouseScrollUnit::Pixel);
+    assert_eq!(mouse_wheel_events[1].x, -3.0);
+
+    // Reading the events does not affect the accumulated movement
+    assert_eq!(input_streams.mouse_wheel_events().len(), 2);
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(-3.0, DEFAULT_PIXELS_PER_LINE)
+    );
+}
+
+#[test]
+fn mouse_wheel_scroll_source_priority() {
+    let mut app = test_app();
+
+    // A wheel and a trackpad scroll in the same frame
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 1.0,
+    });
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: 0.0,
+        y: 3.0,
+    });
+
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(0.0, DEFAULT_PIXELS_PER_LINE + 3.0)
+    );
+
+    input_streams.scroll_source_priority = ScrollSourcePriority::PreferPixel;
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(0.0, 3.0)
+    );
+
+    input_streams.scroll_source_priority = ScrollSourcePriority::PreferLine;
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(0.0, DEFAULT_PIXELS_PER_LINE)
+    );
+}
+
+#[test]
+fn mouse_wheel_max_scroll_per_frame() {
+    let mut app = test_app();
+
+    // A burst of high-resolution scroll events
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    for _ in 0..10 {
+        events.send(MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: -20.0,
+            y: 50.0,
+        });
+    }
+
+    app.update();
+
+    let mut input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(-200.0, 500.0)
+    );
+
+    input_streams.max_scroll_per_frame = Some(Vec2::new(100.0, 100.0));
+    assert_eq!(
+        input_streams.total_mouse_wheel_movement(),
+        Vec2::new(-100.0, 100.0)
+    );
+}
+
+#[test]
+fn mouse_wheel_axis_is_signed() {
+    let mut app = test_app();
+
+    // Scroll up, then scroll further back down
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: 0.0,
+        y: 10.0,
+    });
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: 0.0,
+        y: -25.0,
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.mouse_wheel_axis(MouseWheelAxisType::Y), -15.0);
+    assert_eq!(input_streams.mouse_wheel_axis(MouseWheelAxisType::X), 0.0);
+}
+
+#[test]
+fn mouse_wheel_notches_and_smooth() {
+    let mut app = test_app();
+
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    // A mouse wheel clicking through two notches
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 2.0,
+    });
+    // A trackpad scrolling smoothly
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Pixel,
+        x: 3.5,
+        y: -1.5,
+    });
+
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.mouse_wheel_notches(), IVec2::new(0, 2));
+    assert_eq!(input_streams.mouse_wheel_smooth(), Vec2::new(3.5, -1.5));
+}
+
+#[test]
+fn mouse_wheel_events_split_at_checkpoint() {
+    let mut app = test_app();
+    app.update();
+
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    for y in [1.0, 2.0] {
+        events.send(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y,
+        });
+    }
+
+    let checkpoint = InputStreams::from_world(&app.world, None).mouse_wheel_checkpoint();
+    assert_eq!(checkpoint, 2);
+
+    // This event arrives after the checkpoint, later in the same frame
+    let mut events = app.world.resource_mut::<Events<MouseWheel>>();
+    events.send(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 3.0,
+    });
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let (before, after) = input_streams.split_mouse_wheel_events(checkpoint);
+    let before: Vec<f32> = before.iter().map(|event| event.y).collect();
+    let after: Vec<f32> = after.iter().map(|event| event.y).collect();
+    assert_eq!(before, vec![1.0, 2.0]);
+    assert_eq!(after, vec![3.0]);
+}
+
+#[test]
+fn mouse_wheel_scroll_while_held() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::ScrollWhileHeld {
+            button: MouseButton::Middle,
+        },
+        AxislikeTestAction::Y,
+    )]));
+
+    // Scrolling is ignored while the middle mouse button is released
+    app.send_input(SingleAxis::from_value(MouseWheelAxisType::Y, 5.0));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(AxislikeTestAction::Y));
+    assert_eq!(action_state.value(AxislikeTestAction::Y), 0.0);
+
+    // Holding the middle mouse button lets scrolling through
+    app.send_input(MouseButton::Middle);
+    app.send_input(SingleAxis::from_value(MouseWheelAxisType::Y, 5.0));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::Y));
+    assert_eq!(action_state.value(AxislikeTestAction::Y), 5.0);
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::Y).unwrap(),
+        DualAxisData::new(0.0, 5.0)
+    );
+}
@@ -0,0 +1,727 @@
+use bevy::input::gamepad::GamepadEventRaw;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::input_streams::InputStreams;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::InputKind;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Flashlight,
+    ChargedShot,
+    Spell,
+    Dash,
+    Confirm,
+    Throttle,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>();
+
+    app
+}
+
+#[test]
+fn toggle_flips_on_each_press() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Toggle {
+            input: InputKind::Keyboard(KeyCode::F),
+        },
+        Action::Flashlight,
+    )]));
+
+    // The first press turns the toggle on
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Flashlight));
+
+    // Releasing the button does not change the toggle
+    app.release_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Flashlight));
+
+    // The second press turns the toggle off again
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Flashlight));
+}
+
+#[test]
+fn toggle_works_with_a_preexisting_input_history() {
+    use leafwing_input_manager::input_history::InputHistory;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .init_resource::<InputHistory>()
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::new([(
+            UserInput::Toggle {
+                input: InputKind::Keyboard(KeyCode::F),
+            },
+            Action::Flashlight,
+        )]));
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Flashlight));
+}
+
+#[test]
+fn toggle_flips_on_scan_code_presses() {
+    use bevy::input::keyboard::ScanCode;
+
+    let mut app = test_app();
+    let key_location = InputKind::KeyLocation(ScanCode(33));
+    app.insert_resource(InputMap::new([(
+        UserInput::Toggle {
+            input: key_location,
+        },
+        Action::Flashlight,
+    )]));
+
+    app.send_input(key_location);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Flashlight));
+
+    app.release_input(key_location);
+    app.update();
+    app.send_input(key_location);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Flashlight));
+}
+
+#[test]
+fn charge_reports_hold_duration_on_release() {
+    const MAX_CHARGE: Duration = Duration::from_millis(200);
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Charge {
+            input: InputKind::Mouse(MouseButton::Left),
+            max: MAX_CHARGE,
+        },
+        Action::ChargedShot,
+    )]));
+
+    // Charging does not fire the input
+    app.send_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::ChargedShot));
+
+    std::thread::sleep(MAX_CHARGE / 2);
+
+    // Releasing fires the input, with a value based on how long it was held
+    app.release_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::ChargedShot));
+    let charge = action_state.value(Action::ChargedShot);
+    assert!((0.5..0.75).contains(&charge), "charge was {charge}");
+
+    // The charge is only reported for a single frame
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::ChargedShot));
+    assert_eq!(action_state.value(Action::ChargedShot), 0.0);
+}
+
+#[test]
+fn hold_progress_measures_partial_holds() {
+    const HOLD_DURATION: Duration = Duration::from_millis(200);
+    const INPUT: InputKind = InputKind::Keyboard(KeyCode::E);
+
+    let mut app = test_app();
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.hold_progress(&INPUT, HOLD_DURATION), 0.0);
+
+    app.send_input(KeyCode::E);
+    app.update();
+
+    std::thread::sleep(HOLD_DURATION / 4);
+
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let progress = input_streams.hold_progress(&INPUT, HOLD_DURATION);
+    assert!((0.25..1.0).contains(&progress), "progress was {progress}");
+
+    app.release_input(KeyCode::E);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.hold_progress(&INPUT, HOLD_DURATION), 0.0);
+}
+
+#[test]
+fn axis_cross_fires_once() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxisCross::new(GamepadAxisType::LeftStickX, 0.8),
+        Action::Dash,
+    )]));
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    // Below the threshold
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+
+    // Crossing the threshold fires
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.9));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::Dash));
+
+    // Holding past the threshold does not fire again
+    for _ in 0..3 {
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.released(Action::Dash));
+    }
+
+    // Dropping back below and crossing again fires again
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.2));
+    app.update();
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.9));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::Dash));
+}
+
+#[test]
+fn axis_cross_is_tracked_per_player() {
+    let mut app = test_app();
+    let player_one = Gamepad { id: 1 };
+    let player_two = Gamepad { id: 2 };
+
+    let mut entities = Vec::new();
+    for gamepad in [player_one, player_two] {
+        let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+        gamepad_events.send(GamepadEventRaw {
+            gamepad,
+            event_type: GamepadEventType::Connected,
+        });
+
+        let mut input_map = InputMap::new([(
+            AxisCross::new(GamepadAxisType::LeftStickX, 0.8),
+            Action::Dash,
+        )]);
+        input_map.set_gamepad(gamepad);
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle(InputManagerBundle::<Action> {
+                input_map,
+                ..Default::default()
+            })
+            .id();
+        entities.push(entity);
+    }
+    app.update();
+    app.update();
+
+    // The second player holds their stick past the threshold
+    app.send_input_as_gamepad(
+        SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.9),
+        Some(player_two),
+    );
+    app.send_input_as_gamepad(
+        SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.5),
+        Some(player_one),
+    );
+    app.update();
+    app.update();
+
+    // The first player crosses the threshold, which is not hidden by the second player's stick
+    app.send_input_as_gamepad(
+        SingleAxis::from_value(GamepadAxisType::LeftStickX, 0.9),
+        Some(player_one),
+    );
+    app.update();
+
+    let action_state = app.world.get::<ActionState<Action>>(entities[0]).unwrap();
+    assert!(action_state.just_pressed(Action::Dash));
+    let action_state = app.world.get::<ActionState<Action>>(entities[1]).unwrap();
+    assert!(action_state.released(Action::Dash));
+}
+
+#[test]
+fn gamepad_ever_connected_survives_disconnection() {
+    let mut app = test_app();
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.gamepad_ever_connected());
+
+    for event_type in [GamepadEventType::Connected, GamepadEventType::Disconnected] {
+        let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+        gamepad_events.send(GamepadEventRaw {
+            gamepad: Gamepad { id: 1 },
+            event_type,
+        });
+        app.update();
+        app.update();
+
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(input_streams.gamepad_ever_connected());
+    }
+
+    // The gamepad is no longer connected, but the flag is kept
+    assert_eq!(app.world.resource::<Gamepads>().iter().count(), 0);
+}
+
+#[test]
+fn circle_gesture_fires_after_full_rotation() {
+    use std::f32::consts::{FRAC_PI_4, TAU};
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        CircleGesture {
+            axis: DualAxis::left_stick(),
+            min_angle_swept: TAU - 0.01,
+            window: Duration::from_secs(1),
+        },
+        Action::Spell,
+    )]));
+
+    // Gestures are read from a gamepad, so one must be registered
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    // Trace a circle with the stick, one eighth of a turn each frame
+    for step in 0..=8 {
+        let angle = step as f32 * FRAC_PI_4;
+
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(
+            action_state.released(Action::Spell),
+            "pressed at step {step}"
+        );
+
+        app.send_input(DualAxis::from_value(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+            angle.cos(),
+            angle.sin(),
+        ));
+        app.update();
+    }
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Spell));
+}
+
+#[test]
+fn pressed_together_within_window() {
+    const PARRY: InputKind = InputKind::Keyboard(KeyCode::Q);
+    const COUNTER: InputKind = InputKind::Mouse(MouseButton::Right);
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    let mut app = test_app();
+
+    app.send_input(KeyCode::Q);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_streams.pressed_together(&PARRY, &COUNTER, WINDOW));
+
+    // The second press arrives one frame later
+    app.send_input(MouseButton::Right);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_streams.pressed_together(&PARRY, &COUNTER, WINDOW));
+    assert!(input_streams.pressed_together(&COUNTER, &PARRY, WINDOW));
+}
+
+#[test]
+fn hold_tap_fires_when_tapped_mid_hold() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::HoldTap {
+            hold: InputKind::Keyboard(KeyCode::LShift),
+            tap: InputKind::Keyboard(KeyCode::T),
+        },
+        Action::Spell,
+    )]));
+
+    // Pressing both buttons at once is not a hold-tap
+    app.send_input(KeyCode::LShift);
+    app.send_input(KeyCode::T);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Spell));
+
+    app.release_input(KeyCode::T);
+    app.update();
+
+    // Tapping while the hold continues fires for a single frame
+    app.send_input(KeyCode::T);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::Spell));
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Spell));
+}
+
+#[test]
+fn shake_needs_rapid_reversals() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Shake {
+            axis: GamepadAxisType::LeftStickX.into(),
+            reversals: 4,
+            window: Duration::from_secs(1),
+        },
+        Action::Spell,
+    )]));
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEventRaw>>();
+    gamepad_events.send(GamepadEventRaw {
+        gamepad: Gamepad { id: 1 },
+        event_type: GamepadEventType::Connected,
+    });
+    app.update();
+    app.update();
+
+    // A slow wiggle only changes direction once
+    for value in [0.2, 0.4, 0.6, 0.4, 0.2, 0.0] {
+        app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, value));
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.released(Action::Spell));
+    }
+
+    // Rapidly shaking the stick from side to side reverses its direction every frame
+    for value in [0.9, -0.9, 0.9, -0.9, 0.9] {
+        app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, value));
+        app.update();
+    }
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Spell));
+}
+
+#[test]
+fn debounced_ignores_rapid_presses() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Debounced {
+            input: InputKind::Keyboard(KeyCode::Return),
+            cooldown: Duration::from_secs(5),
+        },
+        Action::Confirm,
+    )]));
+
+    app.send_input(KeyCode::Return);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::Confirm));
+
+    app.release_input(KeyCode::Return);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Confirm));
+
+    // The second press is within the cooldown, so is ignored
+    app.send_input(KeyCode::Return);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Confirm));
+}
+
+#[test]
+fn on_edge_press_fires_when_pressed() {
+    use leafwing_input_manager::user_input::TriggerEdge;
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::OnEdge {
+            input: InputKind::Keyboard(KeyCode::Space),
+            edge: TriggerEdge::Press,
+        },
+        Action::Dash,
+    )]));
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::Dash));
+
+    // Holding the button does not fire again
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+
+    app.release_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+}
+
+#[test]
+fn on_edge_release_fires_when_released() {
+    use leafwing_input_manager::user_input::TriggerEdge;
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::OnEdge {
+            input: InputKind::Mouse(MouseButton::Left),
+            edge: TriggerEdge::Release,
+        },
+        Action::ChargedShot,
+    )]));
+
+    // Drawing the bow does nothing
+    app.send_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::ChargedShot));
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::ChargedShot));
+
+    // Releasing it fires for a single frame
+    app.release_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::ChargedShot));
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::ChargedShot));
+}
+
+#[test]
+fn virtual_dpad_ramp_eases_in_held_keys() {
+    const RAMP: Duration = Duration::from_millis(200);
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        VirtualDPad::wasd().with_ramp(RAMP),
+        Action::Throttle,
+    )]));
+
+    // A freshly pressed key starts near zero
+    app.send_input(KeyCode::W);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.value(Action::Throttle) < 0.5);
+
+    std::thread::sleep(RAMP + RAMP / 4);
+
+    // Once held for the full ramp, the key reports its full value
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.value(Action::Throttle), 1.0);
+    assert_eq!(
+        action_state.axis_pair(Action::Throttle).unwrap().xy(),
+        Vec2::new(0.0, 1.0)
+    );
+}
+
+#[test]
+fn double_tap_hold_fires_while_second_press_is_held() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::DoubleTapHold {
+            input: InputKind::Keyboard(KeyCode::W),
+            tap_window: Duration::from_millis(200),
+        },
+        Action::Dash,
+    )]));
+
+    // The first tap alone does nothing
+    app.send_input(KeyCode::W);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+
+    app.release_input(KeyCode::W);
+    app.update();
+
+    // Pressing again quickly fires the input, for as long as it is held
+    app.send_input(KeyCode::W);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Dash));
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Dash));
+
+    app.release_input(KeyCode::W);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+}
+
+#[test]
+fn double_tap_hold_ignores_slow_taps() {
+    const TAP_WINDOW: Duration = Duration::from_millis(100);
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::DoubleTapHold {
+            input: InputKind::Keyboard(KeyCode::W),
+            tap_window: TAP_WINDOW,
+        },
+        Action::Dash,
+    )]));
+
+    app.send_input(KeyCode::W);
+    app.update();
+    app.release_input(KeyCode::W);
+    app.update();
+
+    std::thread::sleep(TAP_WINDOW * 2);
+
+    // The second press began too long after the first tap
+    app.send_input(KeyCode::W);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Dash));
+}
+
+#[test]
+fn staircase_requires_steps_held_in_order() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Staircase {
+            steps: vec![
+                KeyCode::Key1.into(),
+                KeyCode::Key2.into(),
+                KeyCode::Key3.into(),
+            ],
+        },
+        Action::Spell,
+    )]));
+
+    // Each step is pressed while the earlier steps are held
+    for key in [KeyCode::Key1, KeyCode::Key2] {
+        app.send_input(key);
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.released(Action::Spell));
+    }
+
+    app.send_input(KeyCode::Key3);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Spell));
+}
+
+#[test]
+fn staircase_resets_when_earlier_step_is_released() {
+    use leafwing_input_manager::input_history::InputHistory;
+
+    let steps = vec![
+        KeyCode::Key1.into(),
+        KeyCode::Key2.into(),
+        KeyCode::Key3.into(),
+    ];
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        UserInput::Staircase {
+            steps: steps.clone(),
+        },
+        Action::Spell,
+    )]));
+
+    app.send_input(KeyCode::Key1);
+    app.update();
+    app.send_input(KeyCode::Key2);
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<InputHistory>()
+            .staircase_progress(&steps, None),
+        2
+    );
+
+    // Releasing and re-pressing the first step places it after the second
+    app.release_input(KeyCode::Key1);
+    app.update();
+    app.send_input(KeyCode::Key1);
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<InputHistory>()
+            .staircase_progress(&steps, None),
+        1
+    );
+
+    app.send_input(KeyCode::Key3);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Spell));
+}
+
+#[test]
+fn tap_or_hold_resolves_on_release() {
+    use leafwing_input_manager::input_streams::TapHold;
+
+    const HOLD_THRESHOLD: Duration = Duration::from_millis(100);
+    let input = InputKind::Keyboard(KeyCode::E);
+
+    let mut app = test_app();
+
+    // A quick press is a tap
+    app.send_input(KeyCode::E);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.tap_or_hold(&input, HOLD_THRESHOLD), None);
+
+    app.release_input(KeyCode::E);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.tap_or_hold(&input, HOLD_THRESHOLD),
+        Some(TapHold::Tap)
+    );
+
+    // A long press is a hold
+    app.send_input(KeyCode::E);
+    app.update();
+    std::thread::sleep(HOLD_THRESHOLD * 2);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(input_streams.tap_or_hold(&input, HOLD_THRESHOLD), None);
+
+    app.release_input(KeyCode::E);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert_eq!(
+        input_streams.tap_or_hold(&input, HOLD_THRESHOLD),
+        Some(TapHold::Hold)
+    );
+}
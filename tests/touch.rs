@@ -0,0 +1,91 @@
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::input_streams::InputStreams;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::InputKind;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Aim,
+    Zoom,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>();
+
+    app
+}
+
+fn send_touch(app: &mut App, phase: TouchPhase, id: u64, position: Vec2) {
+    let mut touch_events = app.world.resource_mut::<Events<TouchInput>>();
+    touch_events.send(TouchInput {
+        phase,
+        position,
+        force: None,
+        id,
+    });
+}
+
+#[test]
+fn touch_fingers_are_addressed_by_index() {
+    let mut app = test_app();
+
+    // Touches are ordered by id, not by the order in which their events were sent
+    send_touch(&mut app, TouchPhase::Started, 7, Vec2::new(30.0, 40.0));
+    send_touch(&mut app, TouchPhase::Started, 3, Vec2::new(10.0, 20.0));
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+
+    let first: UserInput = InputKind::TouchFinger { index: 0 }.into();
+    assert!(input_streams.input_pressed(&first));
+    assert_eq!(
+        input_streams.input_axis_pair(&first),
+        Some(DualAxisData::new(10.0, 20.0))
+    );
+
+    let second: UserInput = InputKind::TouchFinger { index: 1 }.into();
+    assert!(input_streams.input_pressed(&second));
+    assert_eq!(
+        input_streams.input_axis_pair(&second),
+        Some(DualAxisData::new(30.0, 40.0))
+    );
+
+    // There is no third touch
+    let third: UserInput = InputKind::TouchFinger { index: 2 }.into();
+    assert!(!input_streams.input_pressed(&third));
+    assert_eq!(input_streams.input_axis_pair(&third), None);
+}
+
+#[test]
+fn pinch_spreading_apart_is_positive() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(UserInput::Pinch, Action::Zoom)]));
+
+    send_touch(&mut app, TouchPhase::Started, 0, Vec2::new(0.0, 0.0));
+    send_touch(&mut app, TouchPhase::Started, 1, Vec2::new(10.0, 0.0));
+    app.update();
+
+    // No previous distance has been recorded yet
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Zoom));
+
+    send_touch(&mut app, TouchPhase::Moved, 0, Vec2::new(-10.0, 0.0));
+    send_touch(&mut app, TouchPhase::Moved, 1, Vec2::new(20.0, 0.0));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::Zoom));
+    assert_eq!(action_state.value(Action::Zoom), 20.0);
+
+    // Holding the touches still does not pinch
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(Action::Zoom));
+}